@@ -0,0 +1,77 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        // Packed textures are drawn by their names, like any other texture
+        ctx.draw_tilemap("world", None, None, None, 0)?;
+
+        for i in 0..16 {
+            let position = (40 + (i % 4) * 180, 40 + (i / 4) * 180);
+            ctx.draw_texture_at(format!("dot{}", i), position, 1)?;
+        }
+
+        ctx.draw(
+            Sprite::new("person").position((400., 400.)).scale((3., 3.)),
+            2,
+        );
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+    texture_manager.add_texture("ground", "./examples/assets/tileset/ground.png");
+    texture_manager.add_texture("water", "./examples/assets/tileset/water.png");
+
+    for i in 0..16 {
+        let mut image = Image::new(16 + i * 4, 16 + i * 4);
+        image.map_pixels(|_| Color::RGB(255 - i as u8 * 16, 64, i as u8 * 16));
+        texture_manager.add_texture_from_image(&format!("dot{}", i), &image);
+    }
+
+    // Everything above ends up in a single texture, written to `target/atlas` to inspect it
+    texture_manager.set_atlas(
+        AtlasOptions::new()
+            .page_size(1024, 1024)
+            .padding(2)
+            .extrude(1)
+            .dump("./target/atlas"),
+    );
+
+    let mut tileset = TileSet::new();
+    tileset.add_tile_type("ground");
+    tileset.add_tile_type("water");
+
+    let mut tilemap = TileMap::new(25, 25, (32, 32));
+    tilemap.add_tileset(tileset);
+    tilemap.add_tile_placement_fn(Box::new(|x, y| (0, (x + y) as usize % 2)));
+
+    GameBuilder::<MyGame>::init("Atlas", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .add_tilemap("world", tilemap)
+        .run()?;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use mgi::prelude::*;
+
+/// A drawable defined outside of mgi
+struct HealthBar {
+    position: Vec2,
+    health: f32,
+}
+
+impl Drawable for HealthBar {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        let mut draw = ctx.draw_context();
+        draw.set_transform(Transform2D::new(self.position));
+
+        draw.draw_texture("person", None, Rect::new((0, 0), 64, 64))?;
+
+        let (width, height) = (100, 12);
+        draw.fill_rect(Rect::new((-18, 72), width, height), Color::GRAY)?;
+        draw.fill_rect(
+            Rect::new((-18, 72), (width as f32 * self.health) as u32, height),
+            Color::GREEN,
+        )?;
+        draw.draw_rect(Rect::new((-18, 72), width, height), Color::BLACK)?;
+
+        // Everything drawn after this is faded
+        draw.set_tint(Color::RGBA(255, 255, 255, 128));
+        draw.draw_line((-18, 90), (82, 90), Color::RED)?;
+
+        Ok(())
+    }
+}
+
+struct MyGame {
+    running: bool,
+    health: f32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            health: 1.,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        self.health = (self.health - 0.1 * ctx.delta_time()).max(0.);
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(
+            HealthBar {
+                position: Vec2::new(368, 368),
+                health: self.health,
+            },
+            0,
+        );
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Custom Drawable", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
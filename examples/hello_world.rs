@@ -30,13 +30,13 @@ impl Game for MyGame {
     fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
         let (w, h) = (400, 400);
 
-        let pos = (300, 200).into();
-        let mut rect = Rectangle::new(pos, w as u32, h as u32, Color::BLUE);
+        let pos = (300, 200);
+        let mut rect = Rectangle::new(Rect::new(pos, w as u32, h as u32), Color::BLUE);
         rect.fill(false);
         ctx.draw(rect, 1);
 
-        let pos = (ctx.size().x / 2 - w / 2, ctx.size().y / 2 - h / 2).into();
-        let rect = Rectangle::new(pos, w as u32, h as u32, Color::RED);
+        let pos = (ctx.size().x / 2 - w / 2, ctx.size().y / 2 - h / 2);
+        let rect = Rectangle::new(Rect::new(pos, w as u32, h as u32), Color::RED);
         ctx.draw(rect, 0);
 
         Ok(())
@@ -0,0 +1,55 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        // Edit and save the files while the game runs to see the changes
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+        ctx.draw(
+            Sprite::new("person").position((336., 336.)).scale((4., 4.)),
+            1,
+        );
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("bg", "./examples/assets/bg.png");
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Hot reload", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .hot_reload()
+        .run()?;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        for (i, name) in ["embedded", "flipped", "recolored", "cropped", "gradient"]
+            .iter()
+            .enumerate()
+        {
+            ctx.draw(
+                Sprite::new(*name)
+                    .position((50. + i as f32 * 140., 350.))
+                    .scale((4., 4.)),
+                0,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    // Embedded in the binary, so the game doesn't need the file at runtime
+    const PERSON: &[u8] = include_bytes!("assets/person.png");
+
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture_from_bytes("embedded", PERSON);
+
+    // Edited on the CPU before being turned into textures
+    let person = Image::from_bytes(PERSON)?;
+
+    let mut flipped = person.clone();
+    flipped.flip_horizontal();
+    texture_manager.add_texture_from_image("flipped", &flipped);
+
+    let mut recolored = person.clone();
+    recolored.map_pixels(|color| Color::RGBA(color.b, color.r, color.g, color.a));
+    texture_manager.add_texture_from_image("recolored", &recolored);
+
+    let cropped = person.crop(Rect::new((8, 0), 16, 16))?;
+    cropped.save(&std::env::temp_dir().join("cropped.png").to_string_lossy())?;
+    texture_manager.add_texture_from_image("cropped", &cropped);
+
+    let gradient: Vec<u8> = (0..32 * 32)
+        .flat_map(|i| [(i % 32 * 8) as u8, (i / 32 * 8) as u8, 128, 255])
+        .collect();
+    texture_manager.add_texture_from_rgba("gradient", 32, 32, &gradient)?;
+
+    GameBuilder::<MyGame>::init("Image", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    level: Option<usize>,
+}
+
+/// Each level has its own tilemap, with the textures only it uses
+fn level_assets(level: usize) -> AssetGroup {
+    let (tile, water_rows) = if level == 0 {
+        ("ground", 20)
+    } else {
+        ("water", 80)
+    };
+
+    let mut group = AssetGroup::new();
+    group.add_texture("ground", "./examples/assets/tileset/ground.png");
+    group.add_texture("water", "./examples/assets/tileset/water.png");
+    group.add_texture("player", "./examples/assets/person.png");
+
+    let mut tileset = TileSet::new();
+    tileset.add_tile_type(tile);
+    tileset.add_tile_type("water");
+
+    let mut tilemap = TileMap::new(25, 25, (32, 32));
+    tilemap.add_tileset(tileset);
+    tilemap.add_tile_placement_fn(Box::new(move |_, y| {
+        if y < water_rows {
+            return (0, 1);
+        }
+
+        (0, 0)
+    }));
+    group.add_tilemap("level", tilemap);
+
+    group
+}
+
+impl MyGame {
+    fn switch_level(&mut self, ctx: &mut Context, level: usize) -> MgiResult<()> {
+        if self.level == Some(level) {
+            return Ok(());
+        }
+
+        // Only the assets of the current level are kept in memory
+        if let Some(previous) = self.level {
+            ctx.unload_group(&format!("level{}", previous))?;
+        }
+        ctx.load_group(&format!("level{}", level), level_assets(level))?;
+        self.level = Some(level);
+
+        Ok(())
+    }
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            level: None,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        if self.level.is_none() || ctx.is_keydown(Keycode::Num1) {
+            self.switch_level(ctx, 0)?;
+        } else if ctx.is_keydown(Keycode::Num2) {
+            self.switch_level(ctx, 1)?;
+        }
+
+        // Textures can also be loaded and unloaded one by one
+        if ctx.is_keydown(Keycode::B) {
+            ctx.load_texture("bg", "./examples/assets/bg.png")?;
+        } else if ctx.is_keydown(Keycode::N) && ctx.texture_handle("bg").is_some() {
+            ctx.unload_texture("bg")?;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.texture_handle("bg").is_some() {
+            ctx.draw_texture(
+                "bg",
+                None,
+                Some(Rect::new((0, 0), 800, 800)),
+                None,
+                None,
+                Some(0.5),
+                1,
+            )?;
+        }
+
+        ctx.draw_tilemap("level", None, None, None, 0)?;
+        ctx.draw(Sprite::new("player").position((380., 380.)), 2);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    GameBuilder::<MyGame>::init("Levels", (800, 800))?.run()?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Loaded in the background, while the game keeps running
+        if ctx.is_keydown(Keycode::L) && !ctx.is_group_loaded("extra") {
+            let mut group = AssetGroup::new();
+            group.add_texture("ground", "./examples/assets/tileset/ground.png");
+            group.add_texture("water", "./examples/assets/tileset/water.png");
+            group.add_svg_texture("star", "./examples/assets/star.svg", (256, 256));
+            ctx.load_group_async("extra", group)?;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        let loading = ctx.loading();
+
+        if !loading.is_done() {
+            let width = (600. * loading.progress()) as u32;
+            ctx.draw(
+                Rectangle::new(Rect::new((100, 380), width.max(1), 40), Color::BLACK),
+                0,
+            );
+
+            return Ok(());
+        }
+
+        if let Some((_, error)) = loading.errors().next() {
+            return Err(error.into());
+        }
+
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+        ctx.draw(
+            Sprite::new("person").position((336., 336.)).scale((4., 4.)),
+            1,
+        );
+
+        if ctx.is_group_ready("extra") {
+            ctx.draw(Sprite::new("ground").position((100., 100.)), 1);
+            ctx.draw(Sprite::new("water").position((150., 100.)), 1);
+            ctx.draw(Sprite::new("star").position((500., 500.)), 1);
+        }
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("bg", "./examples/assets/bg.png");
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Loading", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .load_textures_async()
+        .run()?;
+
+    Ok(())
+}
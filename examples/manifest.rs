@@ -0,0 +1,49 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+        ctx.draw_tilemap("island", None, None, None, 1)?;
+        ctx.draw(Sprite::new("person").position((100., 80.)), 2);
+        ctx.draw(Sprite::new("star").position((500., 500.)), 2);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    // Every asset is described in the manifest instead of being added by hand
+    GameBuilder::<MyGame>::init("Manifest", (800, 800))?
+        .load_manifest("./examples/assets/manifest.toml")?
+        .run()?;
+
+    Ok(())
+}
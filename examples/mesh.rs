@@ -0,0 +1,86 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    rotation: f32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            rotation: 0.,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        self.rotation += 45. * ctx.delta_time();
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        // A triangle with a color per vertex
+        ctx.draw(
+            Mesh::new(vec![
+                Vertex::new((200., 100.), Color::RED, (0., 0.)),
+                Vertex::new((300., 300.), Color::GREEN, (0., 0.)),
+                Vertex::new((100., 300.), Color::BLUE, (0., 0.)),
+            ]),
+            0,
+        );
+
+        // A textured quad, made of 2 triangles sharing vertices
+        ctx.draw(
+            Mesh::new(vec![
+                Vertex::new((450., 100.), Color::WHITE, (0., 0.)),
+                Vertex::new((650., 150.), Color::WHITE, (1., 0.)),
+                Vertex::new((600., 350.), Color::WHITE, (1., 1.)),
+                Vertex::new((500., 300.), Color::YELLOW, (0., 1.)),
+            ])
+            .indices(vec![0, 1, 2, 0, 2, 3])
+            .texture("person"),
+            0,
+        );
+
+        // A concave polygon, triangulated and rotated through a scene
+        let star: Vec<Vec2f> = (0..10)
+            .map(|i| {
+                let radius = if i % 2 == 0 { 150. } else { 60. };
+                Vec2f::new(radius, 0.).rotate(Rotation::Degrees(i as f32 * 36.))
+            })
+            .collect();
+
+        let mut scene = SceneGraph::new();
+        let node = scene.add_node(
+            None,
+            Transform2D::new((400., 600.)).rotation(Rotation::Degrees(self.rotation)),
+        )?;
+        scene.set_drawable(
+            node,
+            Mesh::from_polygon(&star, Color::RGBA(255, 200, 0, 200))?,
+        )?;
+        ctx.draw_scene(&scene, 0);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Mesh", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
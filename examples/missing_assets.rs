@@ -0,0 +1,68 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Strict makes drawing the missing textures return an error, which stops the game
+        if ctx.is_keydown(Keycode::S) {
+            ctx.set_missing_asset_policy(MissingAssetPolicy::Silent);
+        } else if ctx.is_keydown(Keycode::W) {
+            ctx.set_missing_asset_policy(MissingAssetPolicy::WarnOnce);
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(
+            Sprite::new("person").position((200., 336.)).scale((4., 4.)),
+            0,
+        );
+
+        // Never added: drawn as a checkerboard with `WarnOnce`, or not at all with `Silent`
+        ctx.draw(
+            Sprite::new("enemy").position((472., 336.)).scale((8., 8.)),
+            0,
+        );
+        ctx.draw_tilemap("world", None, None, None, 0)?;
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    // One of the tile types has no texture
+    let mut tileset = TileSet::new();
+    tileset.add_tile_type("person");
+    tileset.add_tile_type("grass");
+
+    let mut tilemap = TileMap::new(25, 4, (32, 32));
+    tilemap.add_tileset(tileset);
+    tilemap.add_tile_placement_fn(Box::new(|x, _| (0, x as usize % 2)));
+
+    GameBuilder::<MyGame>::init("Missing assets", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .add_tilemap("world", tilemap)
+        .missing_asset_policy(MissingAssetPolicy::WarnOnce)
+        .run()?;
+
+    Ok(())
+}
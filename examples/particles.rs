@@ -0,0 +1,78 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    fire: ParticleEmitter,
+    sparks: ParticleEmitter,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        let fire = ParticleEmitter::new((400, 600))
+            .spawn_area(60., 10.)
+            .spawn_rate(300.)
+            .lifetime(0.5..=1.5)
+            .velocity(-20.0..=20.0, -160.0..=-80.0)
+            .color_over_life(
+                Curve::linear(Color::YELLOW, Color::RGB(120, 120, 120)).key(0.4, Color::RED),
+            )
+            .alpha_over_life(Curve::linear(1., 0.))
+            .size_over_life(Curve::linear(12., 2.))
+            .blend_mode(BlendMode::Add);
+
+        let mut sparks = ParticleEmitter::new((400, 300))
+            .lifetime(0.5..=1.0)
+            .velocity(-300.0..=300.0, -300.0..=100.0)
+            .gravity((0., 500.))
+            .color_over_life(Curve::constant(Color::YELLOW))
+            .alpha_over_life(Curve::linear(1., 0.));
+        sparks.set_emitting(false);
+
+        Self {
+            running: true,
+            fire,
+            sparks,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        if ctx.is_keydown(Keycode::Space) {
+            self.sparks.burst(200);
+        }
+
+        self.fire.update(ctx.delta_time());
+        self.sparks.update(ctx.delta_time());
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        let size = ctx.size();
+        ctx.draw(
+            Rectangle::new(
+                Rect::new((0, 0), size.x as u32, size.y as u32),
+                Color::BLACK,
+            ),
+            0,
+        );
+
+        ctx.draw_particles(&self.fire, 1)?;
+        ctx.draw_particles(&self.sparks, 1)?;
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    GameBuilder::<MyGame>::init("Particles", (800, 800))?.run()?;
+
+    Ok(())
+}
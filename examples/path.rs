@@ -0,0 +1,102 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        // A line chart
+        let values = [120., 80., 150., 60., 170., 110., 190.];
+        let chart = values
+            .iter()
+            .enumerate()
+            .fold(Path::new(), |path, (i, value)| {
+                let point = (50. + i as f32 * 50., 250. - value);
+                if i == 0 {
+                    path.move_to(point)
+                } else {
+                    path.line_to(point)
+                }
+            })
+            .stroke(
+                Stroke::new(6., Color::CYAN)
+                    .join(LineJoin::Round)
+                    .cap(LineCap::Round),
+            );
+        ctx.draw(chart, 0);
+
+        // Curves
+        ctx.draw(
+            Path::new()
+                .move_to((450., 200.))
+                .quad_to((550., 50.), (650., 200.))
+                .cubic_to((700., 300.), (400., 350.), (450., 200.))
+                .close()
+                .fill(Color::RGB(80, 40, 120), FillRule::NonZero)
+                .stroke(Stroke::new(4., Color::WHITE).join(LineJoin::Miter)),
+            0,
+        );
+
+        // A self intersecting star, with a hole in the middle when filled with the even-odd rule
+        let star = (0..5).fold(Path::new(), |path, i| {
+            let point = Vec2f::new(0., -150.).rotate(Rotation::Degrees(i as f32 * 144.))
+                + Vec2f::new(200., 550.);
+            if i == 0 {
+                path.move_to(point)
+            } else {
+                path.line_to(point)
+            }
+        });
+        ctx.draw(
+            star.clone().close().fill(Color::YELLOW, FillRule::EvenOdd),
+            0,
+        );
+        ctx.draw(
+            star.close()
+                .fill(Color::YELLOW, FillRule::NonZero)
+                .stroke(Stroke::new(3., Color::RED).join(LineJoin::Bevel)),
+            1,
+        );
+
+        // A pie chart slice
+        ctx.draw(
+            Path::new()
+                .move_to((600., 550.))
+                .arc(
+                    (600., 550.),
+                    120.,
+                    Rotation::Degrees(-90.),
+                    Rotation::Degrees(120.),
+                )
+                .close()
+                .fill(Color::RGBA(255, 100, 100, 200), FillRule::NonZero)
+                .stroke(Stroke::new(2., Color::BLACK)),
+            0,
+        );
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    GameBuilder::<MyGame>::init("Path", (800, 800))?.run()?;
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    time: f32,
+    screen: Option<PixelBuffer>,
+    sand: Option<PixelBuffer>,
+    sand_x: u32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            time: 0.,
+            screen: None,
+            sand: None,
+            sand_x: 0,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Pixel buffers can only be created once the game is running
+        if self.screen.is_none() {
+            let screen = ctx.create_pixel_buffer("screen", (200, 200))?;
+            ctx.set_backbuffer(Some(&screen));
+            self.screen = Some(screen);
+            self.sand = Some(ctx.create_pixel_buffer("sand", (64, 64))?);
+        }
+
+        // A plasma effect, rewriting the whole backbuffer every frame
+        self.time += ctx.delta_time();
+        let screen = self.screen.as_mut().unwrap();
+        let (width, height) = screen.size();
+        let mut pixels = screen.pixels_mut();
+        for y in 0..height {
+            for x in 0..width {
+                let v = ((x as f32 / 16. + self.time).sin()
+                    + (y as f32 / 12. - self.time).cos()
+                    + ((x + y) as f32 / 20.).sin())
+                    / 3.;
+                let idx = ((y * width + x) * 4) as usize;
+                pixels[idx..idx + 4].copy_from_slice(&[
+                    (128. + 127. * v) as u8,
+                    (128. + 127. * (v * 2.).sin()) as u8,
+                    200,
+                    255,
+                ]);
+            }
+        }
+
+        // Dropping grains of sand, only uploading the pixels that changed
+        let sand = self.sand.as_mut().unwrap();
+        self.sand_x = (self.sand_x + 7) % 64;
+        sand.set_pixel(self.sand_x, 0, Color::YELLOW);
+        for y in (0..63).rev() {
+            for x in 0..64 {
+                if sand.pixel(x, y) == Some(Color::YELLOW)
+                    && sand.pixel(x, y + 1) != Some(Color::YELLOW)
+                {
+                    sand.set_pixel(x, y, Color::RGBA(0, 0, 0, 0));
+                    sand.set_pixel(x, y + 1, Color::YELLOW);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(
+            Sprite::new("sand").position((272., 272.)).scale((4., 4.)),
+            0,
+        );
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    GameBuilder::<MyGame>::init("Pixel Buffer", (800, 800))?.run()?;
+
+    Ok(())
+}
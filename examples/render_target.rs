@@ -0,0 +1,67 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    panel: Option<RenderTarget>,
+    minimap: Option<RenderTarget>,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            panel: None,
+            minimap: None,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // The panel is only drawn once, and then reused every frame
+        if self.panel.is_none() {
+            let panel = ctx.create_render_target("panel", (200, 100))?;
+            ctx.draw_to_target(&panel, |ctx| {
+                ctx.draw(Rectangle::new(Rect::new((0, 0), 200, 100), Color::GRAY), 0);
+                ctx.draw(Rectangle::new(Rect::new((10, 10), 180, 80), Color::BLUE), 1);
+                Ok(())
+            })?;
+            self.panel = Some(panel);
+        }
+
+        // Everything on layer 0 is drawn into the minimap instead of the window
+        if self.minimap.is_none() {
+            let mut minimap = ctx.create_render_target("minimap", (800, 800))?;
+            minimap.set_clear_color(Color::BLACK);
+            ctx.set_layer_target(0, Some(&minimap));
+            self.minimap = Some(minimap);
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(Sprite::new("bg"), 0);
+        ctx.draw(Sprite::new("minimap").scale((0.25, 0.25)), 1);
+        ctx.draw(Sprite::new("panel").position((550, 650)), 1);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("bg", "./examples/assets/bg.png");
+
+    GameBuilder::<MyGame>::init("Render Target", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
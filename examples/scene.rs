@@ -0,0 +1,87 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    scene: SceneGraph,
+    body: Option<NodeId>,
+    arm: Option<NodeId>,
+    angle: f32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            scene: SceneGraph::new(),
+            body: None,
+            arm: None,
+            angle: 0.,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Build the scene once: the arm (and the weapon held by it) follow the body
+        if self.body.is_none() {
+            let body = self
+                .scene
+                .add_node(None, Transform2D::new((400, 400)).scale((3., 3.)))?;
+            self.scene
+                .set_drawable(body, Sprite::new("person").origin((16, 16)))?;
+
+            let arm = self.scene.add_node(Some(body), Transform2D::new((16, 0)))?;
+            self.scene
+                .set_drawable(arm, Rectangle::new(Rect::new((0, -2), 16, 4), Color::BLUE))?;
+
+            let weapon = self.scene.add_node(Some(arm), Transform2D::new((16, 0)))?;
+            self.scene.set_drawable(
+                weapon,
+                Sprite::new("person").origin((16, 16)).scale((0.5, 0.5)),
+            )?;
+
+            self.body = Some(body);
+            self.arm = Some(arm);
+        }
+
+        self.angle += 90. * ctx.delta_time();
+        if let (Some(body), Some(arm)) = (self.body, self.arm) {
+            let body_transform = self.scene.transform(body)?;
+            self.scene.set_transform(
+                body,
+                body_transform.rotation(Rotation::Degrees(self.angle / 4.)),
+            )?;
+
+            let arm_transform = self.scene.transform(arm)?;
+            self.scene
+                .set_transform(arm, arm_transform.rotation(Rotation::Degrees(self.angle)))?;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(Sprite::new("bg"), 0);
+        ctx.draw_scene(&self.scene, 1);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("bg", "./examples/assets/bg.png");
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Scene", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
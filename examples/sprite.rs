@@ -0,0 +1,64 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    angle: f32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            angle: 0.,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        self.angle += 1.;
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw(Sprite::new("bg"), 0);
+
+        // Spin around the center of the sprite
+        let person = Sprite::new("person")
+            .position((400, 400))
+            .origin((16, 16))
+            .scale((4., 4.))
+            .rotation(Rotation::Degrees(self.angle));
+        ctx.draw(person, 1);
+
+        let person = Sprite::new("person")
+            .position((150, 600))
+            .scale((3., 3.))
+            .flip(true, false)
+            .color_tint(Color::RED)
+            .alpha_tint(0.5)
+            .blend_mode(BlendMode::Add);
+        ctx.draw(person, 1);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("bg", "./examples/assets/bg.png");
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Sprite", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    scale: f32,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            scale: 1.,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Re-rasterize the icons when zooming in and out, so they stay crisp
+        if ctx.is_keydown(Keycode::Up) {
+            self.scale = (self.scale * 2.).min(8.);
+            ctx.set_svg_scale(self.scale)?;
+        }
+        if ctx.is_keydown(Keycode::Down) {
+            self.scale = (self.scale / 2.).max(1.);
+            ctx.set_svg_scale(self.scale)?;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        let size = (32. * self.scale) as u32;
+
+        ctx.draw_texture(
+            "star",
+            None,
+            Some(Rect::new((50, 50), size, size)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+        ctx.draw_texture(
+            "star_large",
+            None,
+            Some(Rect::new((300, 300), 256, 256)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+
+    // Rasterized at the size declared by the SVG
+    texture_manager.add_texture("star", "./examples/assets/star.svg");
+
+    // Rasterized at a requested size
+    texture_manager.add_svg_texture("star_large", "./examples/assets/star.svg", (256, 256));
+
+    GameBuilder::<MyGame>::init("SVG", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
@@ -18,22 +18,51 @@ impl Game for MyGame {
             self.running = false;
         }
 
+        if ctx.is_keydown(Keycode::I) {
+            let info = ctx.texture_info("bg")?;
+            println!(
+                "bg: {}x{} {:?}, {} bytes (all textures: {} bytes)",
+                info.width,
+                info.height,
+                info.format,
+                info.memory,
+                ctx.texture_memory_usage()
+            );
+        }
+
         Ok(())
     }
 
     fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
-        ctx.draw_texture("bg", None, None, None, None, None, 0)?;
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
 
         ctx.draw_texture(
             "person",
             None,
-            Some(Rectangle::new((400, 520).into(), 128, 128, Color::WHITE)),
+            Some(Rect::new((400, 520), 128, 128)),
             Some(Rotation::Degrees(30.)),
             None,
             None,
             1,
         )?;
 
+        // Centered in the window at its natural size
+        let (width, height) = ctx.texture_size("person")?;
+        let size = ctx.size();
+        ctx.draw_texture_at(
+            "person",
+            ((size.x - width as i32) / 2, (size.y - height as i32) / 2),
+            1,
+        )?;
+
         Ok(())
     }
 }
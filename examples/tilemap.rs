@@ -2,11 +2,15 @@ use mgi::prelude::*;
 
 struct MyGame {
     running: bool,
+    world: Option<Handle<TileMap>>,
 }
 
 impl Game for MyGame {
     fn init() -> Self {
-        Self { running: true }
+        Self {
+            running: true,
+            world: None,
+        }
     }
 
     fn is_running(&self) -> bool {
@@ -18,23 +22,38 @@ impl Game for MyGame {
             self.running = false;
         }
 
+        // Handles are faster to look up than names, and are checked when used
+        if self.world.is_none() {
+            self.world = ctx.tilemap_handle("world");
+        }
+
         Ok(())
     }
 
     fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
-        ctx.draw_texture("bg", None, None, None, None, None, 0)?;
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
 
         ctx.draw_texture(
             "person",
             None,
-            Some(Rectangle::new((400, 520).into(), 128, 128, Color::WHITE)),
+            Some(Rect::new((400, 520), 128, 128)),
             Some(Rotation::Degrees(30.)),
             None,
             None,
             2,
         )?;
 
-        ctx.draw_tilemap(0, None, Some(0.5), 0);
+        if let Some(world) = self.world {
+            ctx.draw_tilemap(world, None, Some(0.5), None, 0)?;
+        }
 
         Ok(())
     }
@@ -63,7 +82,7 @@ fn main() -> MgiResult<()> {
 
     GameBuilder::<MyGame>::init("TileMap", (800, 800))?
         .add_texture_manager(texture_manager)
-        .add_tilemap(tilemap)
+        .add_tilemap("world", tilemap)
         .run()?;
 
     Ok(())
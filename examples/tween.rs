@@ -0,0 +1,88 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+    position: TweenValue<Vec2f>,
+    color: TweenValue<Color>,
+    rotation: TweenValue<Rotation>,
+    intro: Option<AnimationId>,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self {
+            running: true,
+            position: TweenValue::new(Vec2f::new(-100., 400.)),
+            color: TweenValue::new(Color::RED),
+            rotation: TweenValue::new(Rotation::Degrees(0.)),
+            intro: None,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        // Slide in, then spin while pulsing the color
+        if self.intro.is_none() {
+            let intro = Sequence::new()
+                .then(
+                    Tween::new(&self.position, Vec2f::new(400., 400.), 1.5).easing(Easing::BackOut),
+                )
+                .wait(0.25)
+                .then(
+                    Parallel::new()
+                        .with(
+                            Tween::new(&self.rotation, Rotation::Degrees(360.), 2.)
+                                .easing(Easing::ElasticOut),
+                        )
+                        .with(
+                            Tween::new(&self.color, Color::BLUE, 0.5)
+                                .repeat(3)
+                                .yoyo(true)
+                                .on_complete(|| println!("Done pulsing!")),
+                        ),
+                );
+
+            self.intro = Some(ctx.play(intro));
+        }
+
+        if ctx
+            .finished_animations()
+            .iter()
+            .any(|id| Some(*id) == self.intro)
+        {
+            println!("Intro finished!");
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        let person = Sprite::new("person")
+            .position(self.position.get())
+            .origin((16, 16))
+            .scale((4., 4.))
+            .rotation(self.rotation.get())
+            .color_tint(self.color.get());
+        ctx.draw(person, 0);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    let mut texture_manager = TextureManager::new();
+    texture_manager.add_texture("person", "./examples/assets/person.png");
+
+    GameBuilder::<MyGame>::init("Tween", (800, 800))?
+        .add_texture_manager(texture_manager)
+        .run()?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use mgi::prelude::*;
+
+struct MyGame {
+    running: bool,
+}
+
+impl Game for MyGame {
+    fn init() -> Self {
+        Self { running: true }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn update(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        if ctx.is_keydown(Keycode::Escape) || ctx.is_keydown(Keycode::Backspace) {
+            self.running = false;
+        }
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut mgi::prelude::Context) -> MgiResult<()> {
+        ctx.draw_texture(
+            "bg",
+            None,
+            Some(Rect::new((0, 0), 800, 800)),
+            None,
+            None,
+            None,
+            0,
+        )?;
+        ctx.draw_tilemap("island", None, None, None, 1)?;
+        ctx.draw(Sprite::new("person").position((100., 80.)), 2);
+
+        Ok(())
+    }
+}
+
+fn main() -> MgiResult<()> {
+    // Usually done once with `mgi-pack examples/assets assets.mgipack` when shipping the game
+    let pack = std::env::temp_dir().join("assets.mgipack");
+    let pack = pack.to_string_lossy();
+    mgi::vfs::write_pack("./examples/assets", &pack)?;
+
+    let mut builder =
+        GameBuilder::<MyGame>::init("Virtual filesystem", (800, 800))?.mount_pack(&pack, 0)?;
+
+    // Files in the mods directory override the ones in the pack
+    if std::path::Path::new("./examples/mods").is_dir() {
+        builder = builder.mount_directory("./examples/mods", 10)?;
+    }
+
+    // Paths are relative to the mounts
+    builder.load_manifest("manifest.toml")?.run()?;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::{
+    handle::Handle,
+    image::Image,
+    texture_manager::{Texture, TextureSource},
+    tilemap::TileMap,
+};
+
+/// Assets that are loaded and unloaded together with `Context::load_group` and
+/// `Context::unload_group`, e.g. the assets of a level, so that only the assets in use are kept in
+/// memory.
+///
+/// The assets are removed with the group, so their names can't already be in use when the group
+/// is loaded.
+#[derive(Default)]
+pub struct AssetGroup {
+    pub(crate) textures: Vec<GroupTexture>,
+    pub(crate) tilemaps: Vec<(String, TileMap)>,
+}
+
+pub(crate) struct GroupTexture {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) source: Option<TextureSource>,
+}
+
+impl AssetGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See `TextureManager::add_texture`
+    pub fn add_texture(&mut self, name: &str, path: &str) {
+        self.push_texture(name, path, None);
+    }
+
+    /// See `TextureManager::add_svg_texture`
+    pub fn add_svg_texture(&mut self, name: &str, path: &str, size: (u32, u32)) {
+        self.push_texture(name, path, Some(TextureSource::Svg(Some(size))));
+    }
+
+    /// See `TextureManager::add_texture_from_bytes`
+    pub fn add_texture_from_bytes(&mut self, name: &str, bytes: &[u8]) {
+        self.push_texture(name, "", Some(TextureSource::Bytes(bytes.to_vec())));
+    }
+
+    pub fn add_texture_from_image(&mut self, name: &str, image: &Image) {
+        let source = TextureSource::Rgba(image.size(), image.pixels().to_vec());
+        self.push_texture(name, "", Some(source));
+    }
+
+    pub fn add_tilemap(&mut self, name: &str, tilemap: TileMap) {
+        self.tilemaps.push((name.into(), tilemap));
+    }
+
+    fn push_texture(&mut self, name: &str, path: &str, source: Option<TextureSource>) {
+        self.textures.push(GroupTexture {
+            name: name.into(),
+            path: path.into(),
+            source,
+        });
+    }
+}
+
+/// The assets of a group that was loaded, which are unloaded with it
+#[derive(Default)]
+pub(crate) struct LoadedGroup {
+    pub(crate) textures: Vec<Handle<Texture>>,
+    pub(crate) tilemaps: Vec<Handle<TileMap>>,
+}
@@ -0,0 +1,436 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    image::Image,
+    prelude::{MgiResult, Rect},
+};
+
+/// How the textures of a `TextureManager` are packed into atlases, see
+/// `TextureManager::set_atlas`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasOptions {
+    page_size: (u32, u32),
+    padding: u32,
+    extrude: u32,
+    pub(crate) dump_directory: Option<String>,
+}
+
+impl Default for AtlasOptions {
+    fn default() -> Self {
+        Self {
+            page_size: (2048, 2048),
+            padding: 2,
+            extrude: 1,
+            dump_directory: None,
+        }
+    }
+}
+
+impl AtlasOptions {
+    /// Pages of 2048x2048 pixels, with 2 pixels of padding and 1 pixel of extrusion
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum size of a page (pages are trimmed to the textures they contain). Textures that
+    /// don't fit on a page are loaded as separate textures.
+    pub fn page_size(mut self, width: u32, height: u32) -> Self {
+        self.page_size = (width, height);
+        self
+    }
+
+    /// The number of transparent pixels between the textures
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// The number of times the edge pixels of each texture are repeated around it, so that
+    /// scaled or rotated textures don't bleed into their neighbours
+    pub fn extrude(mut self, extrude: u32) -> Self {
+        self.extrude = extrude;
+        self
+    }
+
+    /// Writes the pages (`page0.png`, `page1.png`, ...) and where each texture was packed
+    /// (`atlas.toml`) into the directory once the atlas is packed, to inspect it
+    pub fn dump(mut self, directory: &str) -> Self {
+        self.dump_directory = Some(directory.into());
+        self
+    }
+}
+
+/// Where a texture was packed
+pub(crate) struct AtlasRegion {
+    pub(crate) page: usize,
+
+    /// The texture's pixels inside the page, without the extrusion
+    pub(crate) rect: Rect,
+}
+
+pub(crate) struct Atlas {
+    pub(crate) pages: Vec<Image>,
+
+    /// By texture name
+    pub(crate) regions: BTreeMap<String, AtlasRegion>,
+}
+
+impl Atlas {
+    /// Packs the images, largest first, with the MaxRects algorithm: each image goes where it
+    /// leaves the least space on its shorter side, in the first page it fits in. Images that are
+    /// too large for a page aren't packed.
+    pub(crate) fn pack(options: &AtlasOptions, mut images: Vec<(String, Image)>) -> Self {
+        images.sort_by_key(|(name, image)| {
+            let (width, height) = image.size();
+            (std::cmp::Reverse(width.max(height)), name.clone())
+        });
+
+        // Every image takes up its extrusion and the padding on its right and bottom sides, so
+        // the pages are made larger by the padding for the last row and column
+        let margin = options.extrude * 2 + options.padding;
+        let (page_width, page_height) = options.page_size;
+
+        let mut pages: Vec<Page> = Vec::new();
+        let mut placed = Vec::new();
+        for (name, image) in images {
+            let (width, height) = image.size();
+            let cell = (width + margin, height + margin);
+            if width == 0
+                || height == 0
+                || cell.0 > page_width + options.padding
+                || cell.1 > page_height + options.padding
+            {
+                continue;
+            }
+
+            let found = pages
+                .iter_mut()
+                .enumerate()
+                .find_map(|(idx, page)| page.insert(cell).map(|pos| (idx, pos)));
+            let (page, (x, y)) = match found {
+                Some(found) => found,
+                None => {
+                    let mut page =
+                        Page::new((page_width + options.padding, page_height + options.padding));
+                    let pos = page.insert(cell).unwrap();
+                    pages.push(page);
+                    (pages.len() - 1, pos)
+                }
+            };
+
+            let rect = Rect::new(
+                (x + options.extrude as i32, y + options.extrude as i32),
+                width,
+                height,
+            );
+            placed.push((name, image, AtlasRegion { page, rect }));
+        }
+
+        let mut page_images: Vec<Image> = pages
+            .iter()
+            .map(|page| {
+                Image::new(
+                    page.used.0.saturating_sub(options.padding),
+                    page.used.1.saturating_sub(options.padding),
+                )
+            })
+            .collect();
+
+        let mut regions = BTreeMap::new();
+        for (name, image, region) in placed {
+            blit(
+                &mut page_images[region.page],
+                &image,
+                region.rect,
+                options.extrude,
+            );
+            regions.insert(name, region);
+        }
+
+        Self {
+            pages: page_images,
+            regions,
+        }
+    }
+
+    /// Writes the pages and the descriptor of the atlas into the directory
+    pub(crate) fn dump(&self, directory: &str) -> MgiResult<()> {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Couldn't create the directory `{}`: {}", directory, e))?;
+
+        let mut descriptor = Descriptor {
+            pages: Vec::new(),
+            textures: BTreeMap::new(),
+        };
+
+        for (idx, page) in self.pages.iter().enumerate() {
+            let file = format!("page{}.png", idx);
+            page.save(&Path::new(directory).join(&file).to_string_lossy())?;
+            descriptor.pages.push(DescriptorPage {
+                file,
+                width: page.width(),
+                height: page.height(),
+            });
+        }
+        for (name, region) in &self.regions {
+            descriptor.textures.insert(
+                name.clone(),
+                DescriptorRegion {
+                    page: region.page,
+                    x: region.rect.x,
+                    y: region.rect.y,
+                    width: region.rect.width,
+                    height: region.rect.height,
+                },
+            );
+        }
+
+        let path = Path::new(directory).join("atlas.toml");
+        let descriptor = toml::to_string(&descriptor)
+            .map_err(|e| format!("Couldn't write the atlas descriptor: {}", e))?;
+        fs::write(&path, descriptor)
+            .map_err(|e| format!("Couldn't write `{}`: {}", path.display(), e))?;
+
+        Ok(())
+    }
+}
+
+/// The free space of a page, as the (overlapping) largest free rectangles
+struct Page {
+    free: Vec<Rect>,
+
+    /// The size of the area containing every image
+    used: (u32, u32),
+}
+
+impl Page {
+    fn new((width, height): (u32, u32)) -> Self {
+        Self {
+            free: vec![Rect::new((0, 0), width, height)],
+            used: (0, 0),
+        }
+    }
+
+    /// Returns the top-left corner of the space taken by the cell
+    fn insert(&mut self, (width, height): (u32, u32)) -> Option<(i32, i32)> {
+        // The best short side fit, then the best long side fit
+        let best = self
+            .free
+            .iter()
+            .filter(|free| free.width >= width && free.height >= height)
+            .min_by_key(|free| {
+                let (dx, dy) = (free.width - width, free.height - height);
+                (dx.min(dy), dx.max(dy))
+            })?;
+        let placed = Rect::new(best.position(), width, height);
+
+        // Every free rectangle overlapping the placed one is split into the parts around it
+        let mut free = Vec::with_capacity(self.free.len() + 4);
+        for rect in &self.free {
+            if !rect.overlaps(&placed) {
+                free.push(*rect);
+                continue;
+            }
+
+            if placed.left() > rect.left() {
+                let width = (placed.left() - rect.left()) as u32;
+                free.push(Rect::new(rect.position(), width, rect.height));
+            }
+            if placed.right() < rect.right() {
+                let width = (rect.right() - placed.right()) as u32;
+                free.push(Rect::new((placed.right(), rect.y), width, rect.height));
+            }
+            if placed.top() > rect.top() {
+                let height = (placed.top() - rect.top()) as u32;
+                free.push(Rect::new(rect.position(), rect.width, height));
+            }
+            if placed.bottom() < rect.bottom() {
+                let height = (rect.bottom() - placed.bottom()) as u32;
+                free.push(Rect::new((rect.x, placed.bottom()), rect.width, height));
+            }
+        }
+
+        // Rectangles inside other ones are redundant
+        let mut idx = 0;
+        while idx < free.len() {
+            // Of identical rectangles, only the first one is kept
+            let contained = free.iter().enumerate().any(|(other, rect)| {
+                other != idx && contains(rect, &free[idx]) && (*rect != free[idx] || other < idx)
+            });
+            if contained {
+                free.swap_remove(idx);
+            } else {
+                idx += 1;
+            }
+        }
+        self.free = free;
+
+        self.used = (
+            self.used.0.max(placed.right() as u32),
+            self.used.1.max(placed.bottom() as u32),
+        );
+
+        Some((placed.x, placed.y))
+    }
+}
+
+fn contains(outer: &Rect, inner: &Rect) -> bool {
+    inner.left() >= outer.left()
+        && inner.top() >= outer.top()
+        && inner.right() <= outer.right()
+        && inner.bottom() <= outer.bottom()
+}
+
+/// Copies the image into `rect` of the page, repeating its edge pixels `extrude` times around it
+fn blit(page: &mut Image, image: &Image, rect: Rect, extrude: u32) {
+    let page_width = page.width() as usize;
+    let (width, height) = (image.width() as i32, image.height() as i32);
+    let extrude = extrude as i32;
+
+    for y in -extrude..height + extrude {
+        let src_y = y.clamp(0, height - 1) as usize;
+        let dest_y = (rect.y + y) as usize;
+
+        for x in -extrude..width + extrude {
+            let src_x = x.clamp(0, width - 1) as usize;
+            let dest_x = (rect.x + x) as usize;
+
+            let src = (src_y * width as usize + src_x) * 4;
+            let dest = (dest_y * page_width + dest_x) * 4;
+            page.pixels_mut()[dest..dest + 4].copy_from_slice(&image.pixels()[src..src + 4]);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Descriptor {
+    pages: Vec<DescriptorPage>,
+    textures: BTreeMap<String, DescriptorRegion>,
+}
+
+#[derive(Serialize)]
+struct DescriptorPage {
+    file: String,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize)]
+struct DescriptorRegion {
+    page: usize,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2::pixels::Color;
+
+    use super::*;
+
+    fn solid(width: u32, height: u32, color: Color) -> Image {
+        let mut image = Image::new(width, height);
+        image.map_pixels(|_| color);
+        image
+    }
+
+    fn options() -> AtlasOptions {
+        AtlasOptions::new().page_size(64, 64).padding(2).extrude(1)
+    }
+
+    #[test]
+    fn inserted_cells_never_overlap() {
+        let bounds = Rect::new((0, 0), 64, 64);
+        let mut page = Page::new(bounds.size());
+        let mut placed: Vec<Rect> = Vec::new();
+
+        for size in [(20, 10), (7, 30), (16, 16), (33, 5), (9, 9), (12, 21)]
+            .iter()
+            .cycle()
+            .take(40)
+        {
+            if let Some(pos) = page.insert(*size) {
+                let rect = Rect::new(pos, size.0, size.1);
+                assert!(contains(&bounds, &rect), "{:?} is outside the page", rect);
+                assert!(placed.iter().all(|other| !other.overlaps(&rect)));
+                placed.push(rect);
+            }
+        }
+
+        assert!(placed.len() > 10);
+        assert!(page.insert((65, 1)).is_none());
+    }
+
+    #[test]
+    fn regions_are_padded_and_extruded() {
+        let colors = [Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW];
+        let images = (0..8)
+            .map(|i| {
+                let color = colors[i % colors.len()];
+                (format!("{}", i), solid(10 + i as u32, 14 - i as u32, color))
+            })
+            .collect();
+        let atlas = Atlas::pack(&options(), images);
+        assert_eq!(atlas.regions.len(), 8);
+
+        // The space each image takes, with its extrusion and the padding on its right and bottom
+        let cell =
+            |rect: &Rect| Rect::new((rect.x - 1, rect.y - 1), rect.width + 4, rect.height + 4);
+        for (name, region) in &atlas.regions {
+            let page = &atlas.pages[region.page];
+            let rect = region.rect;
+            let color = colors[name.parse::<usize>().unwrap() % colors.len()];
+            let page_rect = Rect::new((0, 0), page.width(), page.height());
+            assert!(contains(&page_rect, &rect.inflate(1, 1)));
+
+            for (other_name, other) in &atlas.regions {
+                if other_name != name && other.page == region.page {
+                    assert!(!cell(&rect).overlaps(&cell(&other.rect)));
+                }
+            }
+
+            // The edges are repeated around the image, including the corners
+            for (x, y) in [
+                (rect.left() - 1, rect.top() - 1),
+                (rect.right(), rect.top()),
+                (rect.left(), rect.bottom()),
+                (rect.right(), rect.bottom()),
+            ] {
+                assert_eq!(page.pixel(x as u32, y as u32), Some(color));
+            }
+        }
+    }
+
+    #[test]
+    fn oversized_images_are_skipped() {
+        let images = vec![
+            ("fits".into(), solid(62, 62, Color::RED)),
+            ("too_wide".into(), solid(63, 1, Color::RED)),
+            ("too_tall".into(), solid(1, 100, Color::RED)),
+            ("empty".into(), Image::new(0, 5)),
+        ];
+        let atlas = Atlas::pack(&options(), images);
+
+        assert_eq!(atlas.regions.keys().collect::<Vec<_>>(), ["fits"]);
+        assert_eq!(atlas.regions["fits"].rect, Rect::new((1, 1), 62, 62));
+        assert_eq!(atlas.pages.len(), 1);
+        assert_eq!(atlas.pages[0].size(), (64, 64));
+    }
+
+    #[test]
+    fn images_that_dont_fit_go_on_new_pages() {
+        let images = (0..3)
+            .map(|i| (format!("{}", i), solid(40, 40, Color::RED)))
+            .collect();
+        let atlas = Atlas::pack(&options(), images);
+
+        assert_eq!(atlas.pages.len(), 3);
+        let mut pages: Vec<_> = atlas.regions.values().map(|region| region.page).collect();
+        pages.sort();
+        assert_eq!(pages, [0, 1, 2]);
+    }
+}
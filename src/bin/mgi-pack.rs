@@ -0,0 +1,30 @@
+//! Packs a directory of assets into a single file, which can be mounted with `Vfs::mount_pack`.
+//!
+//! Usage: mgi-pack <directory> <output>
+
+// Included directly rather than through the library, so the tool doesn't need SDL
+#[allow(dead_code)]
+#[path = "../pack.rs"]
+mod pack;
+
+use std::{env, path::Path, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: {} <directory> <output>", args[0]);
+        process::exit(2);
+    }
+
+    let (directory, output) = (&args[1], &args[2]);
+    match pack::write(Path::new(directory), Path::new(output)) {
+        Ok(count) => println!(
+            "Packed {} files from `{}` into `{}`",
+            count, directory, output
+        ),
+        Err(e) => {
+            eprintln!("Couldn't pack `{}` into `{}`: {}", directory, output, e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::os::raw::c_int;
+
+use sdl2::{
+    render::{Canvas, Texture as TextureRaw},
+    sys::{SDL_Renderer, SDL_Texture},
+    video::Window,
+};
+
+use crate::prelude::MgiResult;
+
+// NOTE: These are declared here (instead of using `sdl2::sys`) so that custom blend modes can be
+// passed around as plain integers; they aren't valid values of the `SDL_BlendMode` enum.
+extern "C" {
+    fn SDL_ComposeCustomBlendMode(
+        src_color_factor: u32,
+        dst_color_factor: u32,
+        color_operation: u32,
+        src_alpha_factor: u32,
+        dst_alpha_factor: u32,
+        alpha_operation: u32,
+    ) -> u32;
+    fn SDL_SetRenderDrawBlendMode(renderer: *mut SDL_Renderer, mode: u32) -> c_int;
+    fn SDL_GetRenderDrawBlendMode(renderer: *mut SDL_Renderer, mode: *mut u32) -> c_int;
+    fn SDL_SetTextureBlendMode(texture: *mut SDL_Texture, mode: u32) -> c_int;
+    fn SDL_GetTextureBlendMode(texture: *mut SDL_Texture, mode: *mut u32) -> c_int;
+}
+
+/// How a drawable is combined with what has already been drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// No blending: the destination is replaced (useful for masks)
+    Replace,
+
+    /// Regular alpha blending
+    Alpha,
+
+    /// Additive blending (useful for fire and glows)
+    Add,
+
+    /// Color modulate: `dst = src * dst`
+    Mod,
+
+    /// Color multiply, taking alpha into account (useful for shadows)
+    Mul,
+
+    /// A blend mode composed from factors and operations (not supported by every renderer)
+    Custom(CustomBlendMode),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomBlendMode {
+    pub src_color_factor: BlendFactor,
+    pub dst_color_factor: BlendFactor,
+    pub color_operation: BlendOperation,
+    pub src_alpha_factor: BlendFactor,
+    pub dst_alpha_factor: BlendFactor,
+    pub alpha_operation: BlendOperation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendFactor {
+    Zero = 1,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstColor,
+    OneMinusDstColor,
+    DstAlpha,
+    OneMinusDstAlpha,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendOperation {
+    /// `dst + src`
+    Add = 1,
+
+    /// `dst - src`
+    Subtract,
+
+    /// `src - dst`
+    RevSubtract,
+
+    /// `min(dst, src)`
+    Minimum,
+
+    /// `max(dst, src)`
+    Maximum,
+}
+
+impl BlendMode {
+    fn to_raw(self) -> u32 {
+        match self {
+            BlendMode::Replace => 0x0,
+            BlendMode::Alpha => 0x1,
+            BlendMode::Add => 0x2,
+            BlendMode::Mod => 0x4,
+            BlendMode::Mul => 0x8,
+            BlendMode::Custom(mode) => unsafe {
+                SDL_ComposeCustomBlendMode(
+                    mode.src_color_factor as u32,
+                    mode.dst_color_factor as u32,
+                    mode.color_operation as u32,
+                    mode.src_alpha_factor as u32,
+                    mode.dst_alpha_factor as u32,
+                    mode.alpha_operation as u32,
+                )
+            },
+        }
+    }
+}
+
+/// Sets the draw blend mode of the canvas for the duration of `f`, restoring the previous one
+/// after.
+pub(crate) fn with_canvas_blend_mode<R>(
+    canvas: &mut Canvas<Window>,
+    blend_mode: Option<BlendMode>,
+    f: impl FnOnce(&mut Canvas<Window>) -> MgiResult<R>,
+) -> MgiResult<R> {
+    let blend_mode = match blend_mode {
+        Some(mode) => mode,
+        None => return f(canvas),
+    };
+
+    let renderer = canvas.raw();
+    let mut prev = 0;
+    unsafe {
+        if SDL_GetRenderDrawBlendMode(renderer, &mut prev) != 0
+            || SDL_SetRenderDrawBlendMode(renderer, blend_mode.to_raw()) != 0
+        {
+            return Err(sdl2::get_error().into());
+        }
+    }
+
+    let res = f(canvas);
+
+    unsafe {
+        SDL_SetRenderDrawBlendMode(renderer, prev);
+    }
+
+    res
+}
+
+/// Sets the blend mode of the texture for the duration of `f`, restoring the previous one after.
+pub(crate) fn with_texture_blend_mode<R>(
+    raw: &mut TextureRaw,
+    blend_mode: Option<BlendMode>,
+    f: impl FnOnce(&mut TextureRaw) -> MgiResult<R>,
+) -> MgiResult<R> {
+    let blend_mode = match blend_mode {
+        Some(mode) => mode,
+        None => return f(raw),
+    };
+
+    let texture = raw.raw();
+    let mut prev = 0;
+    unsafe {
+        if SDL_GetTextureBlendMode(texture, &mut prev) != 0
+            || SDL_SetTextureBlendMode(texture, blend_mode.to_raw()) != 0
+        {
+            return Err(sdl2::get_error().into());
+        }
+    }
+
+    let res = f(raw);
+
+    unsafe {
+        SDL_SetTextureBlendMode(texture, prev);
+    }
+
+    res
+}
@@ -1,26 +1,75 @@
+use crate::asset_group::{AssetGroup, LoadedGroup};
+use crate::draw_context::DrawContext;
+use crate::handle::Assets;
+use crate::handle::{AssetKey, Handle};
+use crate::image::Image;
+use crate::loader::{Loader, LoadingState};
+#[cfg(feature = "hot-reload")]
+use crate::manifest::Manifest;
+use crate::missing_asset::{MissingAssetPolicy, MissingAssets};
+use crate::particles::{ParticleBatch, ParticleEmitter};
+use crate::pixel_buffer::PixelBuffer;
+use crate::prelude::BlendMode;
 use crate::prelude::MgiResult;
-use crate::prelude::Rectangle;
+use crate::prelude::Rect;
 use crate::prelude::Rotation;
+use crate::render_target::{with_render_target, RenderTarget};
 use crate::resource_manager::ResourceManager;
-use crate::texture_manager::Texture;
-use std::{cell::RefCell, rc::Rc};
+use crate::scene::{SceneBatch, SceneGraph};
+use crate::texture_manager::{Texture, TextureInfo, TextureManager, TextureRegion, TextureSource};
+use crate::tilemap::TileMap;
+use crate::tween::{Animation, AnimationId, Animations};
+use crate::vfs::Vfs;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
-use sdl2::{keyboard::Keycode, pixels::Color, render::Canvas, video::Window};
+use sdl2::{
+    keyboard::Keycode,
+    pixels::Color,
+    render::{Canvas, Texture as TextureRaw},
+    video::Window,
+};
 
 use crate::{drawable::Drawable, prelude::Vec2};
 
+pub(crate) type Layers = Vec<Vec<Box<dyn Drawable>>>;
+
 pub(crate) struct Renderer {
     pub(crate) canvas: Rc<RefCell<Canvas<Window>>>,
 
-    pub(crate) layers: Rc<RefCell<Vec<Vec<Box<dyn Drawable>>>>>,
+    pub(crate) layers: Rc<RefCell<Layers>>,
+
+    /// Layers that are drawn into a render target instead of the window
+    pub(crate) layer_targets: HashMap<usize, RenderTarget>,
+
+    /// Uploaded before the layers are drawn every frame
+    pub(crate) pixel_buffers: Vec<PixelBuffer>,
+
+    /// Drawn over the whole window, below every layer
+    pub(crate) backbuffer: Option<PixelBuffer>,
 }
 
 pub struct Context {
     pub(crate) size: Vec2,
+    pub(crate) delta_time: f32,
     pub(crate) clear_color: Color,
     pub(crate) key_down: Vec<Keycode>,
     pub(crate) renderer: Renderer,
     pub(crate) resource_manager: ResourceManager,
+    pub(crate) animations: Animations,
+
+    /// The assets of the loaded groups, by group name
+    pub(crate) asset_groups: HashMap<String, LoadedGroup>,
+
+    pub(crate) loader: Loader,
+
+    pub(crate) missing_assets: MissingAssets,
+
+    #[cfg(feature = "hot-reload")]
+    pub(crate) watcher: Option<crate::hot_reload::AssetWatcher>,
 }
 
 impl Context {
@@ -28,91 +77,329 @@ impl Context {
         self.size
     }
 
+    /// The time, in seconds, that the last frame took
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
     pub fn is_keydown(&self, key: Keycode) -> bool {
         self.key_down.contains(&key)
     }
 
+    /// Plays the animation, advancing it every frame (before `Game::update`) until it finishes
+    pub fn play(&mut self, animation: impl Animation + 'static) -> AnimationId {
+        self.animations.play(Box::new(animation))
+    }
+
+    pub fn stop(&mut self, animation: AnimationId) {
+        self.animations.stop(animation);
+    }
+
+    pub fn is_playing(&self, animation: AnimationId) -> bool {
+        self.animations.is_playing(animation)
+    }
+
+    /// The animations that finished this frame
+    pub fn finished_animations(&self) -> &[AnimationId] {
+        self.animations.finished()
+    }
+
+    /// Re-rasterizes every SVG texture at `scale` times its size, e.g. when the resolution of the
+    /// game changes, so they stay crisp.
+    ///
+    /// NOTE: This changes the size of the textures, so they should be drawn with an explicit size
+    pub fn set_svg_scale(&mut self, scale: f32) -> MgiResult<()> {
+        self.resource_manager
+            .texture_manager
+            .as_ref()
+            .ok_or("A `TextureManager` must be added to use SVG textures")?
+            .borrow_mut()
+            .set_svg_scale(scale)
+    }
+
+    /// What happens when an asset that doesn't exist, or that failed to load, is drawn
+    pub fn set_missing_asset_policy(&mut self, policy: MissingAssetPolicy) {
+        self.missing_assets.policy = policy;
+    }
+
+    /// The virtual filesystem the assets are loaded from, which can also be used to read other
+    /// files of the game
+    pub fn vfs(&self) -> &Vfs {
+        &self.resource_manager.vfs
+    }
+
+    /// The drawing surface for custom `Drawable`s
+    pub fn draw_context(&self) -> DrawContext<'_> {
+        DrawContext::new(self)
+    }
+
     pub(crate) fn canvas(&self) -> Rc<RefCell<Canvas<Window>>> {
         Rc::clone(&self.renderer.canvas)
     }
 
-    pub(crate) fn layers(&self) -> Rc<RefCell<Vec<Vec<Box<dyn Drawable>>>>> {
+    pub(crate) fn layers(&self) -> Rc<RefCell<Layers>> {
         Rc::clone(&self.renderer.layers)
     }
 
     pub fn draw<T: Drawable + 'static>(&mut self, drawable: T, layer: usize) {
         let layers = self.layers();
+        let mut layers = layers.borrow_mut();
 
-        if layers.borrow_mut().len() > layer {
-            layers.borrow_mut()[layer].push(Box::new(drawable));
-        } else {
-            layers.borrow_mut().push(vec![Box::new(drawable)])
+        // Layers are identified by their index, so create any missing ones
+        if layers.len() <= layer {
+            layers.resize_with(layer + 1, Vec::new);
         }
+
+        layers[layer].push(Box::new(drawable));
     }
 
-    // TODO: Add simpler function with less params for ease of use
-    pub fn draw_texture(
+    /// Draws the emitter's current particles
+    pub fn draw_particles(&mut self, emitter: &ParticleEmitter, layer: usize) -> MgiResult<()> {
+        let raw = match emitter.texture_key() {
+            Some(texture) => match self.resolve_texture(texture)? {
+                Some(raw) => Some(raw),
+                None => return Ok(()),
+            },
+            None => None,
+        };
+
+        self.draw(ParticleBatch::new(emitter, raw), layer);
+
+        Ok(())
+    }
+
+    /// Draws every node of the scene, with the world transforms computed when the layer is
+    /// rendered
+    pub fn draw_scene(&mut self, scene: &SceneGraph, layer: usize) {
+        self.draw(
+            SceneBatch {
+                scene: scene.clone(),
+            },
+            layer,
+        );
+    }
+
+    /// Creates an off-screen render target, registered as a texture called `name`
+    pub fn create_render_target(
         &mut self,
-        texture_name: &str,
-        src: Option<Rectangle>,
-        dest: Option<Rectangle>,
-        rotation: Option<Rotation>,
-        color_tint: Option<Color>,
-        alpha_tint: Option<f32>,
-        layer: usize,
+        name: &str,
+        size: (u32, u32),
+    ) -> MgiResult<RenderTarget> {
+        self.resource_manager
+            .texture_manager
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .add_render_target(name, size)?;
+
+        Ok(RenderTarget {
+            name: name.into(),
+            size,
+            clear_color: Color::RGBA(0, 0, 0, 0),
+        })
+    }
+
+    /// Immediately draws everything drawn inside `f` into the render target
+    pub fn draw_to_target(
+        &mut self,
+        target: &RenderTarget,
+        f: impl FnOnce(&mut Context) -> MgiResult<()>,
     ) -> MgiResult<()> {
-        // NOTE: The texture must be set before hand!
-        let mut texture_manager = self
-            .resource_manager
+        // Swap in empty layers so that only the draws from `f` are collected
+        let layers = std::mem::take(&mut self.renderer.layers);
+        let res = f(self);
+        let target_layers = std::mem::replace(&mut self.renderer.layers, layers);
+        res?;
+
+        let mut target_layers = target_layers.borrow_mut();
+        self.draw_layers_to_target(target, &mut target_layers)
+    }
+
+    /// Creates a buffer of pixels that can be written directly, registered as a texture called
+    /// `name`
+    pub fn create_pixel_buffer(&mut self, name: &str, size: (u32, u32)) -> MgiResult<PixelBuffer> {
+        self.resource_manager
             .texture_manager
             .as_ref()
             .unwrap()
-            .borrow_mut();
-        let texture = texture_manager.get_texture_mut(texture_name);
+            .borrow_mut()
+            .add_streaming_texture(name, size)?;
 
-        if let Some(texture) = texture {
-            let layers = self.layers();
+        let buffer = PixelBuffer::new(name, size);
+        self.renderer.pixel_buffers.push(buffer.clone());
 
-            let raw = if let Some(r) = &texture.raw {
-                if let Some(alpha) = alpha_tint {
-                    r.borrow_mut().set_alpha_mod((255. * alpha) as u8);
-                }
-                if let Some(color) = color_tint {
-                    r.borrow_mut().set_color_mod(color.r, color.g, color.b);
-                }
+        Ok(buffer)
+    }
 
-                Some(Rc::clone(r))
-            } else {
-                None
-            };
+    /// Draws the pixel buffer stretched over the whole window, below every layer (or stops drawing
+    /// it if `buffer` is `None`)
+    pub fn set_backbuffer(&mut self, buffer: Option<&PixelBuffer>) {
+        self.renderer.backbuffer = buffer.cloned();
+    }
 
-            let rotation = if let Some(rot) = rotation {
-                rot
-            } else {
-                Rotation::Radians(0.0)
-            };
+    /// Draws the given layer into the render target instead of the window (or back into the window
+    /// if `target` is `None`)
+    ///
+    /// NOTE: Layers are drawn in order, so the layer must be below any layer that draws the target
+    pub fn set_layer_target(&mut self, layer: usize, target: Option<&RenderTarget>) {
+        if let Some(target) = target {
+            self.renderer.layer_targets.insert(layer, target.clone());
+        } else {
+            self.renderer.layer_targets.remove(&layer);
+        }
+    }
+
+    /// Draws all the layers, in order, to their targets
+    pub(crate) fn render_layers(&self) -> MgiResult<()> {
+        for buffer in &self.renderer.pixel_buffers {
+            buffer.upload(&mut self.texture_raw(&(&buffer.name).into())?.borrow_mut())?;
+        }
+
+        if let Some(backbuffer) = &self.renderer.backbuffer {
+            let raw = self.texture_raw(&(&backbuffer.name).into())?;
+            self.canvas().borrow_mut().copy(&raw.borrow(), None, None)?;
+        }
+
+        let layers = self.layers();
 
-            if layers.borrow_mut().len() > layer {
-                layers.borrow_mut()[layer].push(Box::new(Texture {
-                    name: texture.name.to_owned(),
-                    path: texture.path.to_owned(),
-                    raw,
-                    src,
-                    rotation,
-                    dest,
-                }));
+        for (idx, layer) in layers.borrow_mut().iter_mut().enumerate() {
+            if let Some(target) = self.renderer.layer_targets.get(&idx) {
+                self.draw_layers_to_target(target, std::slice::from_mut(layer))?;
             } else {
-                layers.borrow_mut().push(vec![Box::new(Texture {
-                    name: texture.name.to_owned(),
-                    path: texture.path.to_owned(),
-                    raw,
-                    src,
-                    dest,
-                    rotation,
-                })])
+                for drawable in layer.iter_mut() {
+                    drawable.draw(self)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The raw texture to draw, with the `MissingAssetPolicy` applied if the texture is missing
+    /// (`None` means nothing should be drawn, e.g. while the texture is loaded in the background)
+    pub(crate) fn resolve_texture(
+        &self,
+        texture: &AssetKey<Texture>,
+    ) -> MgiResult<Option<TextureRegion>> {
+        let texture_manager = self
+            .resource_manager
+            .texture_manager
+            .as_ref()
+            .unwrap()
+            .borrow();
+        if texture_manager.is_loading(texture) {
+            return Ok(None);
+        }
+
+        self.missing_assets
+            .texture(texture_manager.get_region(texture))
+    }
+
+    fn texture_raw(&self, texture: &AssetKey<Texture>) -> MgiResult<Rc<RefCell<TextureRaw>>> {
+        Ok(Rc::clone(
+            self.resource_manager
+                .texture_manager
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .get_raw(texture)?,
+        ))
+    }
+
+    fn draw_layers_to_target(
+        &self,
+        target: &RenderTarget,
+        layers: &mut [Vec<Box<dyn Drawable>>],
+    ) -> MgiResult<()> {
+        let raw = self.texture_raw(&(&target.name).into())?;
+        let raw = raw.borrow();
+        with_render_target(&self.renderer.canvas, &raw, target.clear_color, || {
+            for layer in layers.iter_mut() {
+                for drawable in layer.iter_mut() {
+                    drawable.draw(self)?;
+                }
             }
+
+            Ok(())
+        })
+    }
+
+    /// Draws the whole texture at its natural size, with its top-left corner at `position`
+    pub fn draw_texture_at(
+        &mut self,
+        texture: impl Into<AssetKey<Texture>>,
+        position: impl Into<Vec2>,
+        layer: usize,
+    ) -> MgiResult<()> {
+        let texture = texture.into();
+        let (width, height) = match self.resolve_texture(&texture)? {
+            Some(region) => region.size(),
+            None => return Ok(()),
+        };
+
+        let dest = Rect::new(position, width, height);
+        self.draw_texture(texture, None, Some(dest), None, None, None, layer)
+    }
+
+    /// Draws the `src` region of the texture (or the whole texture) into `dest`. Without `dest`,
+    /// it is drawn at its natural size in the top-left corner.
+    ///
+    /// See [`Sprite`](crate::sprite::Sprite) for a simpler way to draw textures, which can also
+    /// be drawn with a blend mode (`Sprite::blend_mode`)
+    pub fn draw_texture(
+        &mut self,
+        texture: impl Into<AssetKey<Texture>>,
+        src: Option<Rect>,
+        dest: Option<Rect>,
+        rotation: Option<Rotation>,
+        color_tint: Option<Color>,
+        alpha_tint: Option<f32>,
+        layer: usize,
+    ) -> MgiResult<()> {
+        let texture = texture.into();
+        let region = match self.resolve_texture(&texture)? {
+            Some(region) => region,
+            None => return Ok(()),
+        };
+        let region_src = region.src(src);
+        if region_src.is_some_and(|src| src.is_empty()) {
+            return Ok(());
         }
 
+        let dest = dest.unwrap_or_else(|| {
+            let (width, height) = match src {
+                Some(src) => (src.width, src.height),
+                None => region.size(),
+            };
+            Rect::new((0, 0), width, height)
+        });
+
+        let rotation = if let Some(rot) = rotation {
+            rot
+        } else {
+            Rotation::Radians(0.0)
+        };
+
+        // The tint is stored on the draw command, and only applied when it is drawn
+        self.draw(
+            Texture {
+                name: match &texture {
+                    AssetKey::Name(name) => name.clone(),
+                    AssetKey::Handle(handle) => format!("{:?}", handle),
+                },
+                path: String::new(),
+                src: region_src,
+                raw: Some(region.raw),
+                region: None,
+                dest: Some(dest),
+                rotation,
+                color_tint,
+                alpha_tint,
+                blend_mode: None,
+            },
+            layer,
+        );
+
         Ok(())
     }
 
@@ -122,56 +409,427 @@ impl Context {
     // TODO: Add scrolling tilemap? (Need to add camera first)
     pub fn draw_tilemap(
         &mut self,
-        tilemap_id: usize,
+        tilemap: impl Into<AssetKey<TileMap>>,
         color_tint: Option<Color>,
         alpha_tint: Option<f32>,
+        blend_mode: Option<BlendMode>,
         layer: usize,
-    ) {
-        // TODO: Proper error handling
-        let tilemap_manager = self.resource_manager.tilemap_manager.as_ref().unwrap();
-        let texture_manager = self.resource_manager.texture_manager.as_ref().unwrap();
-        let tilemap = &tilemap_manager.borrow()[tilemap_id];
+    ) -> MgiResult<()> {
+        let tilemap_manager = match &self.resource_manager.tilemap_manager {
+            Some(tilemap_manager) => Rc::clone(tilemap_manager),
+            None => return self.missing_assets.report("No tilemaps were added".into()),
+        };
+        let tilemap_manager = tilemap_manager.borrow();
+        let tilemap = match tilemap_manager.get(&tilemap.into()) {
+            Ok(tilemap) => tilemap,
+            Err(e) => return self.missing_assets.report(e),
+        };
+
+        // The textures of the tile types, looked up once each
+        let mut textures = HashMap::new();
 
         for tile in &tilemap.tiles {
-            // NOTE: The texture must be set before hand!
             let texture_name = tilemap.get_texture_name(tile.texture_idx);
+            let region = match textures.get(texture_name) {
+                Some(region) => Option::clone(region),
+                None => {
+                    let region = self.resolve_texture(&texture_name.into())?;
+                    textures.insert(texture_name, region.clone());
+                    region
+                }
+            };
 
-            if let Some(texture) = texture_manager.borrow_mut().get_texture_mut(texture_name) {
-                let layers = self.layers();
-
-                let raw = if let Some(r) = &texture.raw {
-                    if let Some(alpha) = alpha_tint {
-                        r.borrow_mut().set_alpha_mod((255. * alpha) as u8);
-                    }
-                    if let Some(color) = color_tint {
-                        r.borrow_mut().set_color_mod(color.r, color.g, color.b);
-                    }
-
-                    Some(Rc::clone(r))
-                } else {
-                    None
-                };
-
-                if layers.borrow_mut().len() > layer {
-                    layers.borrow_mut()[layer].push(Box::new(Texture {
-                        name: texture.name.to_owned(),
-                        path: texture.path.to_owned(),
-                        raw,
-                        src: None,
-                        dest: Some(tile.rect.clone()),
+            if let Some(region) = region {
+                self.draw(
+                    Texture {
+                        name: texture_name.into(),
+                        path: String::new(),
+                        src: region.src(None),
+                        raw: Some(region.raw),
+                        region: None,
+                        dest: Some(tile.rect),
                         rotation: tile.rotation,
-                    }));
-                } else {
-                    layers.borrow_mut().push(vec![Box::new(Texture {
-                        name: texture.name.to_owned(),
-                        path: texture.path.to_owned(),
-                        raw,
-                        src: None,
-                        dest: Some(tile.rect.clone()),
-                        rotation: tile.rotation,
-                    })])
+                        color_tint,
+                        alpha_tint,
+                        blend_mode,
+                    },
+                    layer,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The handle of the texture with this name
+    pub fn texture_handle(&self, name: &str) -> Option<Handle<Texture>> {
+        self.resource_manager
+            .texture_manager
+            .as_ref()?
+            .borrow()
+            .texture_handle(name)
+    }
+
+    /// The size, pixel format and memory usage of the texture. Fails if it doesn't exist or isn't
+    /// loaded yet.
+    pub fn texture_info(&self, texture: impl Into<AssetKey<Texture>>) -> MgiResult<TextureInfo> {
+        self.texture_manager().borrow().texture_info(texture)
+    }
+
+    /// The width and height of the texture, see `texture_info`
+    pub fn texture_size(&self, texture: impl Into<AssetKey<Texture>>) -> MgiResult<(u32, u32)> {
+        self.texture_manager().borrow().texture_size(texture)
+    }
+
+    /// The memory used by the pixels of every loaded texture, in bytes
+    pub fn texture_memory_usage(&self) -> usize {
+        self.texture_manager().borrow().memory_usage()
+    }
+
+    /// The handle of the tilemap with this name
+    pub fn tilemap_handle(&self, name: &str) -> Option<Handle<TileMap>> {
+        self.resource_manager
+            .tilemap_manager
+            .as_ref()?
+            .borrow()
+            .handle(name)
+    }
+
+    /// Loads a texture while the game is running, replacing the texture with the same name if
+    /// there is one (in which case its handles stay valid)
+    pub fn load_texture(&mut self, name: &str, path: &str) -> MgiResult<Handle<Texture>> {
+        let handle = self
+            .texture_manager()
+            .borrow_mut()
+            .load_texture(name, path, None)?;
+        self.watch_file(path);
+
+        Ok(handle)
+    }
+
+    /// Like `load_texture`, for an encoded image file that is already in memory
+    pub fn load_texture_from_bytes(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+    ) -> MgiResult<Handle<Texture>> {
+        self.texture_manager().borrow_mut().load_texture(
+            name,
+            "",
+            Some(TextureSource::Bytes(bytes.to_vec())),
+        )
+    }
+
+    /// Like `load_texture`, for an image in memory
+    pub fn load_texture_from_image(
+        &mut self,
+        name: &str,
+        image: &Image,
+    ) -> MgiResult<Handle<Texture>> {
+        self.texture_manager().borrow_mut().load_texture(
+            name,
+            "",
+            Some(TextureSource::Rgba(image.size(), image.pixels().to_vec())),
+        )
+    }
+
+    /// Removes the texture and frees it, making its handles stale.
+    ///
+    /// Pixel buffers, backbuffers and layer targets using the texture stop being drawn.
+    pub fn unload_texture(&mut self, texture: impl Into<AssetKey<Texture>>) -> MgiResult<()> {
+        let texture = self
+            .texture_manager()
+            .borrow_mut()
+            .unload_texture(&texture.into())?;
+
+        let renderer = &mut self.renderer;
+        renderer
+            .pixel_buffers
+            .retain(|buffer| buffer.name != texture.name);
+        if matches!(&renderer.backbuffer, Some(buffer) if buffer.name == texture.name) {
+            renderer.backbuffer = None;
+        }
+        renderer
+            .layer_targets
+            .retain(|_, target| target.name != texture.name);
+
+        Ok(())
+    }
+
+    /// Generates and adds a tilemap while the game is running, replacing the tilemap with the same
+    /// name if there is one (in which case its handles stay valid)
+    pub fn load_tilemap(&mut self, name: &str, mut tilemap: TileMap) -> Handle<TileMap> {
+        tilemap.generate();
+
+        self.resource_manager
+            .tilemap_manager
+            .get_or_insert_with(|| Rc::new(RefCell::new(Assets::new("tilemap"))))
+            .borrow_mut()
+            .insert(name, tilemap)
+    }
+
+    /// Removes the tilemap, making its handles stale
+    pub fn unload_tilemap(&mut self, tilemap: impl Into<AssetKey<TileMap>>) -> MgiResult<()> {
+        self.resource_manager
+            .tilemap_manager
+            .as_ref()
+            .ok_or("No tilemaps were added")?
+            .borrow_mut()
+            .remove(&tilemap.into())?;
+
+        Ok(())
+    }
+
+    /// Loads every asset of the group, which can then be unloaded together with `unload_group`.
+    ///
+    /// The assets of a group can't have the names of assets that are already loaded, since they
+    /// would be unloaded with the group. If an asset fails to load, the assets of the group that
+    /// were already loaded are unloaded.
+    pub fn load_group(&mut self, name: &str, group: AssetGroup) -> MgiResult<()> {
+        self.check_group(name, &group)?;
+
+        let mut loaded = LoadedGroup::default();
+        for texture in group.textures {
+            let res = self.texture_manager().borrow_mut().load_texture(
+                &texture.name,
+                &texture.path,
+                texture.source,
+            );
+
+            match res {
+                Ok(handle) => {
+                    self.watch_file(&texture.path);
+                    loaded.textures.push(handle);
+                }
+                Err(e) => {
+                    self.unload_assets(loaded);
+                    return Err(format!("Couldn't load the asset group `{}`: {}", name, e).into());
                 }
             }
         }
+
+        for (tilemap_name, tilemap) in group.tilemaps {
+            loaded
+                .tilemaps
+                .push(self.load_tilemap(&tilemap_name, tilemap));
+        }
+
+        self.asset_groups.insert(name.into(), loaded);
+
+        Ok(())
+    }
+
+    /// Unloads every asset of the group. Assets that were already unloaded are skipped.
+    pub fn unload_group(&mut self, name: &str) -> MgiResult<()> {
+        let loaded = self
+            .asset_groups
+            .remove(name)
+            .ok_or_else(|| format!("The asset group `{}` is not loaded", name))?;
+        self.unload_assets(loaded);
+
+        Ok(())
+    }
+
+    /// Whether the group was loaded (and not unloaded since). A group loaded with
+    /// `load_group_async` counts as loaded as soon as it is queued, even though its textures
+    /// aren't loaded yet: see `is_group_ready`.
+    pub fn is_group_loaded(&self, name: &str) -> bool {
+        self.asset_groups.contains_key(name)
+    }
+
+    /// Whether every texture of the group is loaded and can be drawn. This is never the case if
+    /// one of the textures failed to load in the background (see `loading` for the errors).
+    pub fn is_group_ready(&self, name: &str) -> bool {
+        let group = match self.asset_groups.get(name) {
+            Some(group) => group,
+            None => return false,
+        };
+        let texture_manager = self.texture_manager().borrow();
+
+        group.textures.iter().all(|texture| {
+            let key = AssetKey::Handle(*texture);
+            !texture_manager.is_loading(&key) && texture_manager.get_raw(&key).is_ok()
+        })
+    }
+
+    /// Loads a texture in the background, replacing the texture with the same name (if there is
+    /// one) once it is loaded.
+    ///
+    /// The texture can't be drawn until it is loaded: see `loading` for its status.
+    pub fn load_texture_async(&mut self, name: &str, path: &str) -> Handle<Texture> {
+        self.queue_texture(name, path, None)
+    }
+
+    /// Like `load_group`, but the textures are loaded in the background (see `load_texture_async`).
+    ///
+    /// Textures that fail to load are reported by `loading`, and the rest of the group stays
+    /// loaded.
+    pub fn load_group_async(&mut self, name: &str, group: AssetGroup) -> MgiResult<()> {
+        self.check_group(name, &group)?;
+
+        let mut loaded = LoadedGroup::default();
+        for texture in group.textures {
+            loaded
+                .textures
+                .push(self.queue_texture(&texture.name, &texture.path, texture.source));
+        }
+        for (tilemap_name, tilemap) in group.tilemaps {
+            loaded
+                .tilemaps
+                .push(self.load_tilemap(&tilemap_name, tilemap));
+        }
+
+        self.asset_groups.insert(name.into(), loaded);
+
+        Ok(())
+    }
+
+    /// The status of the assets loaded in the background
+    pub fn loading(&self) -> &LoadingState {
+        self.loader.state()
+    }
+
+    pub(crate) fn queue_texture(
+        &mut self,
+        name: &str,
+        path: &str,
+        source: Option<TextureSource>,
+    ) -> Handle<Texture> {
+        let (handle, svg_scale, vfs) = {
+            let mut texture_manager = self.texture_manager().borrow_mut();
+            (
+                texture_manager.reserve_texture(name, path),
+                texture_manager.svg_scale(),
+                texture_manager.vfs.clone(),
+            )
+        };
+        let source = TextureManager::resolve_source(path, source);
+
+        self.loader
+            .queue(name, handle, path, source, svg_scale, vfs);
+        self.watch_file(path);
+
+        handle
+    }
+
+    /// Uploads the textures decoded since the last frame
+    pub(crate) fn finish_loading(&mut self) {
+        for decoded in self.loader.decoded() {
+            let result = decoded.image.and_then(|image| {
+                self.texture_manager()
+                    .borrow_mut()
+                    .finish_texture(decoded.handle, &decoded.path, decoded.source, &image)
+                    .map_err(|e| e.to_string())
+            });
+
+            self.texture_manager()
+                .borrow_mut()
+                .stop_loading(decoded.handle);
+            self.loader.finish(decoded.index, result);
+        }
+    }
+
+    /// Reloads the assets whose file changed since the last frame
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn reload_changed_assets(&mut self) {
+        if let Some(watcher) = &self.watcher {
+            let changed = watcher.changed();
+            if changed.is_empty() {
+                return;
+            }
+            self.texture_manager().borrow().reload_changed(&changed);
+
+            let vfs = &self.resource_manager.vfs;
+            let manifests: Vec<String> = self
+                .resource_manager
+                .manifests
+                .iter()
+                .filter(
+                    |path| match vfs.disk_path(path).map(std::fs::canonicalize) {
+                        Some(Ok(path)) => changed.contains(&path),
+                        _ => false,
+                    },
+                )
+                .cloned()
+                .collect();
+
+            for path in manifests {
+                if let Err(e) = self.reload_manifest(&path) {
+                    eprintln!("Couldn't reload the manifest `{}`: {}", path, e);
+                }
+            }
+        }
+    }
+
+    /// Loads every asset of the manifest again, replacing the previous ones in place
+    #[cfg(feature = "hot-reload")]
+    fn reload_manifest(&mut self, path: &str) -> MgiResult<()> {
+        let manifest = Manifest::load(&self.resource_manager.vfs, path)?;
+        manifest.validate(&self.resource_manager.vfs)?;
+        manifest.validate_tiles(|name| self.texture_handle(name).is_some())?;
+
+        manifest.reload(self)
+    }
+
+    /// Watches the file for changes, if hot reloading is enabled
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn watch_file(&mut self, path: &str) {
+        // Files read from packs can't change
+        let path = match self.resource_manager.vfs.disk_path(path) {
+            Some(path) => path,
+            None => return,
+        };
+        if let Some(watcher) = &mut self.watcher {
+            watcher.watch(&path);
+        }
+    }
+
+    #[cfg(not(feature = "hot-reload"))]
+    pub(crate) fn watch_file(&mut self, _path: &str) {}
+
+    /// Checks that the group isn't loaded yet, and that its assets only replace their own names
+    fn check_group(&self, name: &str, group: &AssetGroup) -> MgiResult<()> {
+        if self.asset_groups.contains_key(name) {
+            return Err(format!("The asset group `{}` is already loaded", name).into());
+        }
+
+        let mut textures = HashSet::new();
+        for texture in &group.textures {
+            if !textures.insert(&texture.name) || self.texture_handle(&texture.name).is_some() {
+                return Err(format!(
+                    "Couldn't load the asset group `{}`: a texture named `{}` is already loaded",
+                    name, texture.name
+                )
+                .into());
+            }
+        }
+
+        let mut tilemaps = HashSet::new();
+        for (tilemap, _) in &group.tilemaps {
+            if !tilemaps.insert(tilemap) || self.tilemap_handle(tilemap).is_some() {
+                return Err(format!(
+                    "Couldn't load the asset group `{}`: a tilemap named `{}` is already loaded",
+                    name, tilemap
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unload_assets(&mut self, loaded: LoadedGroup) {
+        // The only possible error is a stale handle, for assets that were already unloaded
+        for texture in loaded.textures {
+            let _ = self.unload_texture(texture);
+        }
+        for tilemap in loaded.tilemaps {
+            let _ = self.unload_tilemap(tilemap);
+        }
+    }
+
+    pub(crate) fn texture_manager(&self) -> &Rc<RefCell<TextureManager>> {
+        // There is always a texture manager once the game is running
+        self.resource_manager.texture_manager.as_ref().unwrap()
     }
 }
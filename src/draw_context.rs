@@ -0,0 +1,180 @@
+use sdl2::{pixels::Color, rect::Point};
+
+use crate::{
+    blend::with_canvas_blend_mode, drawable::Rectangle, mesh::Mesh, path::Path, prelude::*,
+    scene::SceneDrawable, sprite::Sprite, transform::Matrix2D,
+};
+
+/// The drawing surface for custom `Drawable`s, created with `Context::draw_context`.
+///
+/// Everything drawn through it has the current transform (e.g. a camera or an offset), tint, and
+/// blend mode applied.
+pub struct DrawContext<'a> {
+    ctx: &'a Context,
+    transform: Matrix2D,
+    tint: Color,
+    blend_mode: Option<BlendMode>,
+}
+
+/// Multiplies the channels of the colors together
+fn modulate(a: Color, b: Color) -> Color {
+    let channel = |a: u8, b: u8| (a as u16 * b as u16 / 255) as u8;
+
+    Color::RGBA(
+        channel(a.r, b.r),
+        channel(a.g, b.g),
+        channel(a.b, b.b),
+        channel(a.a, b.a),
+    )
+}
+
+impl<'a> DrawContext<'a> {
+    pub(crate) fn new(ctx: &'a Context) -> Self {
+        Self {
+            ctx,
+            transform: Matrix2D::IDENTITY,
+            tint: Color::WHITE,
+            blend_mode: None,
+        }
+    }
+
+    /// The size of the window
+    pub fn size(&self) -> Vec2 {
+        self.ctx.size()
+    }
+
+    pub fn transform(&self) -> Matrix2D {
+        self.transform
+    }
+
+    /// Sets the transform applied to everything drawn after this
+    ///
+    /// NOTE: Rectangles can't be rotated, so they're drawn as the bounding box of the transformed
+    /// rectangle
+    pub fn set_transform(&mut self, transform: impl Into<Matrix2D>) {
+        self.transform = transform.into();
+    }
+
+    /// Sets the color (and alpha) that everything drawn after this is multiplied by
+    pub fn set_tint(&mut self, tint: Color) {
+        self.tint = tint;
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: Option<BlendMode>) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) -> MgiResult<()> {
+        self.rectangle(rect, color, true)
+    }
+
+    /// Draws the outline of the rectangle
+    pub fn draw_rect(&mut self, rect: Rect, color: Color) -> MgiResult<()> {
+        self.rectangle(rect, color, false)
+    }
+
+    pub fn draw_line(
+        &mut self,
+        from: impl Into<Vec2f>,
+        to: impl Into<Vec2f>,
+        color: Color,
+    ) -> MgiResult<()> {
+        let from = Vec2::from(self.transform.transform_point(from.into()));
+        let to = Vec2::from(self.transform.transform_point(to.into()));
+
+        self.with_canvas(color, |canvas| {
+            Ok(canvas.draw_line(Point::new(from.x, from.y), Point::new(to.x, to.y))?)
+        })
+    }
+
+    pub fn draw_point(&mut self, point: impl Into<Vec2f>, color: Color) -> MgiResult<()> {
+        let point = Vec2::from(self.transform.transform_point(point.into()));
+
+        self.with_canvas(color, |canvas| {
+            Ok(canvas.draw_point(Point::new(point.x, point.y))?)
+        })
+    }
+
+    pub fn draw_sprite(&mut self, sprite: &Sprite) -> MgiResult<()> {
+        let mut sprite = sprite.clone();
+
+        // Combine the sprite's own tint with the context's
+        let color_tint = modulate(sprite.color_tint.unwrap_or(Color::WHITE), self.tint);
+        sprite.color_tint = Some(color_tint);
+        sprite.alpha_tint = Some(sprite.alpha_tint.unwrap_or(1.) * self.tint.a as f32 / 255.);
+        sprite.blend_mode = sprite.blend_mode.or(self.blend_mode);
+
+        sprite.draw_transformed(self.ctx, &self.transform)
+    }
+
+    pub fn draw_mesh(&mut self, mesh: &Mesh) -> MgiResult<()> {
+        mesh.draw_with(self.ctx, &self.transform, self.tint, self.blend_mode)
+    }
+
+    pub fn draw_path(&mut self, path: &Path) -> MgiResult<()> {
+        self.draw_mesh(&path.to_mesh())
+    }
+
+    /// Draws the region `src` of the texture (or the whole texture if it's `None`) into `dest`
+    pub fn draw_texture(
+        &mut self,
+        texture: impl Into<AssetKey<Texture>>,
+        src: Option<Rect>,
+        dest: Rect,
+    ) -> MgiResult<()> {
+        let texture = texture.into();
+        let mut sprite = Sprite::new(texture.clone()).position(dest.position());
+
+        let (width, height) = match src {
+            Some(src) => {
+                sprite = sprite.src(src);
+                (src.width, src.height)
+            }
+            None => self.texture_size(&texture)?,
+        };
+
+        if width != 0 && height != 0 {
+            sprite = sprite.scale((
+                dest.width as f32 / width as f32,
+                dest.height as f32 / height as f32,
+            ));
+        }
+
+        self.draw_sprite(&sprite)
+    }
+
+    /// Missing textures that aren't drawn have no size
+    fn texture_size(&self, texture: &AssetKey<Texture>) -> MgiResult<(u32, u32)> {
+        Ok(match self.ctx.resolve_texture(texture)? {
+            Some(region) => region.size(),
+            None => (0, 0),
+        })
+    }
+
+    fn rectangle(&mut self, rect: Rect, color: Color, fill: bool) -> MgiResult<()> {
+        let mut rectangle = Rectangle::new(rect, modulate(color, self.tint));
+        rectangle.fill(fill);
+        if let Some(blend_mode) = self.blend_mode {
+            rectangle.blend_mode(blend_mode);
+        }
+
+        rectangle.draw_transformed(self.ctx, &self.transform)
+    }
+
+    fn with_canvas(
+        &mut self,
+        color: Color,
+        f: impl FnOnce(&mut sdl2::render::Canvas<sdl2::video::Window>) -> MgiResult<()>,
+    ) -> MgiResult<()> {
+        let canvas = self.ctx.canvas();
+        let mut canvas = canvas.borrow_mut();
+
+        with_canvas_blend_mode(&mut canvas, self.blend_mode, |canvas| {
+            canvas.set_draw_color(modulate(color, self.tint));
+            let res = f(canvas);
+            canvas.set_draw_color(self.ctx.clear_color);
+
+            res
+        })
+    }
+}
@@ -1,68 +1,68 @@
 use sdl2::pixels::Color;
 
-use crate::prelude::*;
+use crate::{blend::with_canvas_blend_mode, prelude::*};
 
+/// Something that can be drawn on a layer with `Context::draw`.
+///
+/// Custom drawables can draw through `Context::draw_context`.
 pub trait Drawable {
     fn draw(&mut self, ctx: &Context) -> MgiResult<()>;
 }
 
 #[derive(Clone)]
 pub struct Rectangle {
-    pub(crate) position: Vec2,
-    pub(crate) width: u32,
-    pub(crate) height: u32,
+    rect: Rect,
     color: Color,
     fill: bool,
+    blend_mode: Option<BlendMode>,
 }
 
 impl Rectangle {
-    pub fn new(position: Vec2, width: u32, height: u32, color: Color) -> Self {
+    pub fn new(rect: Rect, color: Color) -> Self {
         Self {
-            position,
-            width,
-            height,
+            rect,
             color,
             fill: true,
+            blend_mode: None,
         }
     }
 
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
     pub fn fill(&mut self, val: bool) {
         self.fill = val;
     }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = Some(mode);
+    }
 }
 
 impl Drawable for Rectangle {
     fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
         let canvas = ctx.canvas();
+        let mut canvas = canvas.borrow_mut();
 
-        // Set color of rectangle
-        canvas.borrow_mut().set_draw_color(self.color);
-
-        if self.fill {
-            canvas.borrow_mut().fill_rect(sdl2::rect::Rect::new(
-                self.position.x,
-                self.position.y,
-                self.width,
-                self.height,
-            ))?;
-        } else {
-            canvas.borrow_mut().draw_rect(sdl2::rect::Rect::new(
-                self.position.x,
-                self.position.y,
-                self.width,
-                self.height,
-            ))?;
-        }
+        with_canvas_blend_mode(&mut canvas, self.blend_mode, |canvas| {
+            // Set color of rectangle
+            canvas.set_draw_color(self.color);
 
-        // Reset to clear color
-        canvas.borrow_mut().set_draw_color(ctx.clear_color);
+            if self.fill {
+                canvas.fill_rect(sdl2::rect::Rect::from(self.rect))?;
+            } else {
+                canvas.draw_rect(sdl2::rect::Rect::from(self.rect))?;
+            }
 
-        Ok(())
-    }
-}
+            // Reset to clear color
+            canvas.set_draw_color(ctx.clear_color);
 
-impl From<&Rectangle> for sdl2::rect::Rect {
-    fn from(r: &Rectangle) -> Self {
-        sdl2::rect::Rect::new(r.position.x, r.position.y, r.width, r.height)
+            Ok(())
+        })
     }
 }
@@ -1,10 +1,20 @@
 use crate::context::{Context, Renderer};
+use crate::handle::Assets;
+use crate::loader::Loader;
+use crate::manifest::Manifest;
+use crate::missing_asset::{MissingAssetPolicy, MissingAssets};
 use crate::prelude::TileMap;
 use crate::resource_manager::ResourceManager;
 use crate::texture_manager::TextureManager;
+use crate::tween::Animations;
 use crate::{prelude::MgiResult, utils::Vec2};
 use sdl2::{event::Event, pixels::Color, Sdl, VideoSubsystem};
-use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 pub trait Game {
     fn init() -> Self;
@@ -22,6 +32,13 @@ pub struct GameBuilder<T: Game> {
 
     startup_systems: Vec<fn()>,
     resource_manager: ResourceManager,
+
+    /// Registered when the game starts, once every texture was added
+    manifests: Vec<Manifest>,
+    load_async: bool,
+    missing_asset_policy: MissingAssetPolicy,
+    #[cfg(feature = "hot-reload")]
+    hot_reload: bool,
     game: T,
 }
 
@@ -37,6 +54,11 @@ impl<T: Game> GameBuilder<T> {
             video_sys,
             startup_systems: Vec::new(),
             resource_manager: ResourceManager::new(None, None),
+            manifests: Vec::new(),
+            load_async: false,
+            missing_asset_policy: MissingAssetPolicy::default(),
+            #[cfg(feature = "hot-reload")]
+            hot_reload: false,
             game: T::init(),
         })
     }
@@ -59,26 +81,134 @@ impl<T: Game> GameBuilder<T> {
         self
     }
 
-    /// The ID of the TileMap is its index in the  tilemap vector
-    pub fn add_tilemap(mut self, mut tilemap: TileMap) -> Self {
-        // Initalize tilemap_manager if it doesn't exist
-        if let None = self.resource_manager.tilemap_manager {
-            self.resource_manager.tilemap_manager = Some(Rc::new(RefCell::new(Vec::new())));
+    /// Mounts a directory in the virtual filesystem the assets are loaded from (see `Vfs`)
+    pub fn mount_directory(self, directory: &str, priority: i32) -> MgiResult<Self> {
+        self.resource_manager
+            .vfs
+            .mount_directory(directory, priority)?;
+        Ok(self)
+    }
+
+    /// Mounts a pack made with the `mgi-pack` tool in the virtual filesystem the assets are loaded
+    /// from (see `Vfs`)
+    pub fn mount_pack(self, path: &str, priority: i32) -> MgiResult<Self> {
+        self.resource_manager.vfs.mount_pack(path, priority)?;
+        Ok(self)
+    }
+
+    /// Registers every asset listed in a TOML manifest, checking all the entries up front (the
+    /// tiles of the tilemaps are checked when the game starts, since they can use textures that
+    /// are added later):
+    ///
+    /// ```toml
+    /// [textures]
+    /// # Paths are relative to the manifest
+    /// bg = "bg.png"
+    /// star = { path = "star.svg", size = [64, 64] }
+    ///
+    /// [tilemaps.world]
+    /// tile_size = [32, 32]
+    /// # Texture names, from the manifest or the texture manager
+    /// tiles = ["ground", "water"]
+    /// # Rows of indices into `tiles`, from top to bottom
+    /// grid = [
+    ///     [1, 1, 1],
+    ///     [0, 0, 0],
+    /// ]
+    ///
+    /// # Optional, see `TextureManager::set_atlas`
+    /// [atlas]
+    /// page_size = [1024, 1024]
+    /// padding = 2
+    /// extrude = 1
+    /// # A directory on disk, relative to the working directory
+    /// dump = "atlas_dump"
+    /// ```
+    ///
+    /// mgi has no fonts or sounds, so manifests can't list them.
+    pub fn load_manifest(mut self, path: &str) -> MgiResult<Self> {
+        let manifest = Manifest::load(&self.resource_manager.vfs, path)?;
+        manifest.validate(&self.resource_manager.vfs)?;
+
+        self.resource_manager.manifests.push(path.into());
+        self.manifests.push(manifest);
+
+        Ok(self)
+    }
+
+    /// Adds the assets of the manifests, once the textures added in any order are known
+    fn register_manifests(&mut self) -> MgiResult<()> {
+        if self.manifests.is_empty() {
+            return Ok(());
+        }
+
+        let texture_manager = self
+            .resource_manager
+            .texture_manager
+            .get_or_insert_with(|| Rc::new(RefCell::new(TextureManager::new())));
+        for manifest in &self.manifests {
+            manifest.register_textures(&mut texture_manager.borrow_mut());
+        }
+        for manifest in &self.manifests {
+            manifest
+                .validate_tiles(|name| texture_manager.borrow().texture_handle(name).is_some())?;
         }
 
-        let tilemap_manager = self.resource_manager.tilemap_manager.as_ref().unwrap();
+        let tilemaps = self
+            .resource_manager
+            .tilemap_manager
+            .get_or_insert_with(|| Rc::new(RefCell::new(Assets::new("tilemap"))));
+        for manifest in self.manifests.drain(..) {
+            manifest.register_tilemaps(&mut tilemaps.borrow_mut());
+        }
+
+        Ok(())
+    }
 
-        // Set tilemap ID
-        let tilemap_id = tilemap_manager.borrow().len();
-        tilemap.id = tilemap_id;
+    /// What happens when an asset that doesn't exist, or that failed to load, is drawn (see
+    /// `Context::set_missing_asset_policy` to change it while the game runs)
+    pub fn missing_asset_policy(mut self, policy: MissingAssetPolicy) -> Self {
+        self.missing_asset_policy = policy;
+        self
+    }
 
-        // Add tilemap to tilemap_manager
-        tilemap_manager.borrow_mut().push(tilemap);
+    /// Loads the textures of the texture manager in the background instead of before the first
+    /// frame, so the game can show a loading screen (see `Context::loading`)
+    pub fn load_textures_async(mut self) -> Self {
+        self.load_async = true;
+        self
+    }
+
+    /// Watches the textures loaded from disk, and reloads them in place when their file changes
+    /// (so their names and handles stay valid). Failures are logged, and the previous texture is
+    /// kept.
+    ///
+    /// Manifests (see `load_manifest`) are watched too: when one changes, every texture and
+    /// tilemap it declares is loaded again, replacing the previous ones in place. Assets that were
+    /// removed from the manifest stay loaded.
+    ///
+    /// Meant for development: requires the `hot-reload` feature.
+    #[cfg(feature = "hot-reload")]
+    pub fn hot_reload(mut self) -> Self {
+        self.hot_reload = true;
+        self
+    }
+
+    /// Adds a tilemap, which can be drawn by its name or by the handle returned by
+    /// `Context::tilemap_handle`
+    pub fn add_tilemap(mut self, name: &str, tilemap: TileMap) -> Self {
+        self.resource_manager
+            .tilemap_manager
+            .get_or_insert_with(|| Rc::new(RefCell::new(Assets::new("tilemap"))))
+            .borrow_mut()
+            .insert(name, tilemap);
 
         self
     }
 
     pub fn run(mut self) -> MgiResult<()> {
+        self.register_manifests()?;
+
         // Create window
         let window = self
             .video_sys
@@ -96,24 +226,68 @@ impl<T: Game> GameBuilder<T> {
 
         let mut ctx = Context {
             size: self.size,
+            delta_time: 0.,
             clear_color: Color::WHITE,
             key_down: Vec::new(),
             renderer: Renderer {
                 canvas: Rc::new(RefCell::new(canvas)),
                 layers: Rc::new(RefCell::new(Vec::new())),
+                layer_targets: HashMap::new(),
+                pixel_buffers: Vec::new(),
+                backbuffer: None,
             },
             resource_manager: self.resource_manager.clone(),
+            animations: Animations::default(),
+            asset_groups: HashMap::new(),
+            loader: Loader::new(),
+            missing_assets: MissingAssets::new(),
+            #[cfg(feature = "hot-reload")]
+            watcher: None,
         };
 
+        // Render targets are registered as textures, so there must always be a texture manager
+        if self.resource_manager.texture_manager.is_none() {
+            let texture_manager = Rc::new(RefCell::new(TextureManager::new()));
+            self.resource_manager.texture_manager = Some(Rc::clone(&texture_manager));
+            ctx.resource_manager.texture_manager = Some(texture_manager);
+        }
+
         // Load textures
         if let Some(tm) = &self.resource_manager.texture_manager {
             tm.borrow_mut().texture_creator = Some(ctx.canvas().borrow().texture_creator());
-            tm.borrow_mut().load_textures()?;
+            tm.borrow_mut().vfs = self.resource_manager.vfs.clone();
+
+            let placeholder = tm.borrow().create_placeholder()?;
+            ctx.missing_assets.placeholder = Some(Rc::new(RefCell::new(placeholder)));
+            ctx.missing_assets.policy = self.missing_asset_policy;
+            if !self.load_async {
+                tm.borrow_mut().load_textures()?;
+            }
+        }
+        #[cfg(feature = "hot-reload")]
+        if self.hot_reload {
+            // Like the other hot reloading failures, this is only logged
+            match crate::hot_reload::AssetWatcher::new() {
+                Ok(watcher) => ctx.watcher = Some(watcher),
+                Err(e) => eprintln!("Couldn't watch the assets for hot reloading: {}", e),
+            }
+
+            let textures = ctx.texture_manager().borrow().texture_paths();
+            for path in textures.iter().chain(&self.resource_manager.manifests) {
+                ctx.watch_file(path);
+            }
+        }
+
+        if self.load_async {
+            let textures = ctx.texture_manager().borrow().unloaded_textures();
+            for (name, path, source) in textures {
+                ctx.queue_texture(&name, &path, source);
+            }
         }
 
         // Generate all tilemaps
         if let Some(tm) = &self.resource_manager.tilemap_manager {
-            for tilemap in tm.borrow_mut().iter_mut() {
+            for (_, tilemap) in tm.borrow_mut().iter_mut() {
                 tilemap.generate();
             }
         }
@@ -122,7 +296,12 @@ impl<T: Game> GameBuilder<T> {
         ctx.canvas().borrow_mut().clear();
         ctx.canvas().borrow_mut().present();
         let mut event_pump = self.sdl_ctx.event_pump()?;
+        let mut last_frame = Instant::now();
         'gameloop: while self.game.is_running() {
+            let now = Instant::now();
+            ctx.delta_time = (now - last_frame).as_secs_f32();
+            last_frame = now;
+
             // Handle events
             for event in event_pump.poll_iter() {
                 match event {
@@ -140,17 +319,17 @@ impl<T: Game> GameBuilder<T> {
                 }
             }
 
+            #[cfg(feature = "hot-reload")]
+            ctx.reload_changed_assets();
+            ctx.finish_loading();
+            ctx.animations.update(ctx.delta_time);
             self.game.update(&mut ctx)?;
 
             // The render function doesnt actually render: it just determines the layers to render
             // stuff in, their textures, and their, displayed positions
             self.game.render(&mut ctx)?;
 
-            for layer in ctx.layers().borrow_mut().iter_mut() {
-                for drawable in layer.iter_mut() {
-                    drawable.draw(&ctx)?;
-                }
-            }
+            ctx.render_layers()?;
 
             // // canvas.copy(&texture, None, None)?;
             // canvas.set_draw_color(Color::RED);
@@ -159,6 +338,13 @@ impl<T: Game> GameBuilder<T> {
 
             ctx.canvas().borrow_mut().present();
             ctx.key_down = vec![]; // Reset keys pressed
+            ctx.layers().borrow_mut().clear(); // Drawables are submitted again every frame
+            ctx.resource_manager
+                .texture_manager
+                .as_ref()
+                .unwrap()
+                .borrow_mut()
+                .destroy_released();
             std::thread::sleep(Duration::new(0, 1_000_000_000u32 / 60)); // 60fps
         }
 
@@ -0,0 +1,288 @@
+use std::{collections::HashMap, fmt, hash, marker::PhantomData};
+
+use crate::prelude::MgiResult;
+
+/// Identifies an asset of type `T`, returned when the asset is registered.
+///
+/// Handles are cheap to copy and look up, and they know when the asset they refer to has been
+/// removed: using a stale handle is an error, even if another asset has been added in its place.
+pub struct Handle<T> {
+    index: usize,
+    generation: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    fn new(index: usize, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            marker: PhantomData,
+        }
+    }
+}
+
+// Implemented by hand, so that they don't require `T` to implement them
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> hash::Hash for Handle<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+/// Refers to an asset either by the name it was registered with, or by its handle
+pub enum AssetKey<T> {
+    Name(String),
+    Handle(Handle<T>),
+}
+
+impl<T> Clone for AssetKey<T> {
+    fn clone(&self) -> Self {
+        match self {
+            AssetKey::Name(name) => AssetKey::Name(name.clone()),
+            AssetKey::Handle(handle) => AssetKey::Handle(*handle),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AssetKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetKey::Name(name) => write!(f, "`{}`", name),
+            AssetKey::Handle(handle) => write!(f, "{:?}", handle),
+        }
+    }
+}
+
+impl<T> From<&str> for AssetKey<T> {
+    fn from(name: &str) -> Self {
+        AssetKey::Name(name.into())
+    }
+}
+
+impl<T> From<String> for AssetKey<T> {
+    fn from(name: String) -> Self {
+        AssetKey::Name(name)
+    }
+}
+
+impl<T> From<&String> for AssetKey<T> {
+    fn from(name: &String) -> Self {
+        AssetKey::Name(name.clone())
+    }
+}
+
+impl<T> From<Handle<T>> for AssetKey<T> {
+    fn from(handle: Handle<T>) -> Self {
+        AssetKey::Handle(handle)
+    }
+}
+
+struct Slot<T> {
+    generation: u32,
+    name: String,
+    value: Option<T>,
+}
+
+/// Generational storage for assets, which can be looked up by handle or by name
+pub(crate) struct Assets<T> {
+    /// Used in error messages (e.g. "texture")
+    kind: &'static str,
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    names: HashMap<String, Handle<T>>,
+}
+
+impl<T> Assets<T> {
+    pub(crate) fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            slots: Vec::new(),
+            free: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Adds the asset, replacing the asset with the same name if there is one (in which case its
+    /// handle stays valid)
+    pub(crate) fn insert(&mut self, name: &str, value: T) -> Handle<T> {
+        if let Some(handle) = self.names.get(name) {
+            self.slots[handle.index].value = Some(value);
+            return *handle;
+        }
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.name = name.into();
+                slot.value = Some(value);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    name: name.into(),
+                    value: Some(value),
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        let handle = Handle::new(index, self.slots[index].generation);
+        self.names.insert(name.into(), handle);
+
+        handle
+    }
+
+    /// Removes the asset, making every handle to it stale
+    pub(crate) fn remove(&mut self, key: &AssetKey<T>) -> MgiResult<T> {
+        let handle = self.resolve(key)?;
+        let slot = &mut self.slots[handle.index];
+
+        slot.generation += 1;
+        self.names.remove(&slot.name);
+        self.free.push(handle.index);
+
+        Ok(slot.value.take().unwrap())
+    }
+
+    pub(crate) fn handle(&self, name: &str) -> Option<Handle<T>> {
+        self.names.get(name).copied()
+    }
+
+    pub(crate) fn get(&self, key: &AssetKey<T>) -> MgiResult<&T> {
+        let handle = self.resolve(key)?;
+        Ok(self.slots[handle.index].value.as_ref().unwrap())
+    }
+
+    pub(crate) fn get_mut(&mut self, key: &AssetKey<T>) -> MgiResult<&mut T> {
+        let handle = self.resolve(key)?;
+        Ok(self.slots[handle.index].value.as_mut().unwrap())
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let value = slot.value.as_ref()?;
+            Some((Handle::new(index, slot.generation), value))
+        })
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let value = slot.value.as_mut()?;
+                Some((Handle::new(index, slot.generation), value))
+            })
+    }
+
+    /// Checks that the key refers to an asset that exists, returning its handle
+    fn resolve(&self, key: &AssetKey<T>) -> MgiResult<Handle<T>> {
+        match key {
+            AssetKey::Name(name) => self
+                .names
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("There is no {} named `{}`", self.kind, name).into()),
+            AssetKey::Handle(handle) => match self.slots.get(handle.index) {
+                Some(slot) if slot.generation == handle.generation && slot.value.is_some() => {
+                    Ok(*handle)
+                }
+                Some(_) => Err(format!(
+                    "The {} handle {:?} is stale: its {} was removed",
+                    self.kind, handle, self.kind
+                )
+                .into()),
+                None => Err(format!("The {} handle {:?} does not exist", self.kind, handle).into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_get_by_name_or_handle() {
+        let mut assets = Assets::new("number");
+        let one = assets.insert("one", 1);
+        let two = assets.insert("two", 2);
+
+        assert_ne!(one, two);
+        assert_eq!(assets.handle("one"), Some(one));
+        assert_eq!(*assets.get(&"two".into()).unwrap(), 2);
+        *assets.get_mut(&one.into()).unwrap() += 10;
+        assert_eq!(*assets.get(&one.into()).unwrap(), 11);
+        assert!(assets.get(&"three".into()).is_err());
+    }
+
+    #[test]
+    fn inserting_the_same_name_keeps_the_handle() {
+        let mut assets = Assets::new("number");
+        let handle = assets.insert("one", 1);
+
+        assert_eq!(assets.insert("one", 100), handle);
+        assert_eq!(*assets.get(&handle.into()).unwrap(), 100);
+        assert_eq!(assets.iter().count(), 1);
+    }
+
+    #[test]
+    fn removed_assets_make_their_handles_stale() {
+        let mut assets = Assets::new("number");
+        let one = assets.insert("one", 1);
+
+        assert_eq!(assets.remove(&one.into()).unwrap(), 1);
+        let e = assets.get(&one.into()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!(
+                "The number handle {:?} is stale: its number was removed",
+                one
+            )
+        );
+        assert!(assets.get(&"one".into()).is_err());
+        assert!(assets.remove(&one.into()).is_err());
+
+        // The name and the slot are reused, but the old handle stays stale
+        let new_one = assets.insert("one", 1);
+        assert_eq!(new_one.index, one.index);
+        assert_ne!(new_one, one);
+        assert!(assets.get(&one.into()).is_err());
+        assert_eq!(*assets.get(&"one".into()).unwrap(), 1);
+        assert_eq!(
+            assets.iter().map(|(handle, _)| handle).collect::<Vec<_>>(),
+            [new_one]
+        );
+    }
+
+    #[test]
+    fn handles_from_elsewhere_dont_exist() {
+        let assets: Assets<i32> = Assets::new("number");
+        let e = assets.get(&Handle::new(3, 0).into()).unwrap_err();
+        assert!(e.to_string().contains("does not exist"));
+    }
+}
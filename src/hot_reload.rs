@@ -0,0 +1,76 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::prelude::MgiResult;
+
+/// Watches the directories of the assets loaded from disk, to reload the files that change
+pub(crate) struct AssetWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    directories: HashSet<PathBuf>,
+}
+
+impl AssetWatcher {
+    pub(crate) fn new() -> MgiResult<Self> {
+        let (sender, events) = mpsc::channel();
+
+        Ok(Self {
+            watcher: notify::recommended_watcher(sender)?,
+            events,
+            directories: HashSet::new(),
+        })
+    }
+
+    /// Watches the directory of the file rather than the file itself, since editors often save
+    /// files by replacing them
+    pub(crate) fn watch(&mut self, path: &Path) {
+        let directory = match path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+        let directory = match fs::canonicalize(directory) {
+            Ok(directory) => directory,
+            Err(e) => {
+                eprintln!("Couldn't watch `{}` for changes: {}", path.display(), e);
+                return;
+            }
+        };
+
+        if self.directories.contains(&directory) {
+            return;
+        }
+        match self.watcher.watch(&directory, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                self.directories.insert(directory);
+            }
+            Err(e) => eprintln!(
+                "Couldn't watch `{}` for changes: {}",
+                directory.display(),
+                e
+            ),
+        }
+    }
+
+    /// The files that were created or modified since the last call
+    pub(crate) fn changed(&self) -> HashSet<PathBuf> {
+        let mut changed = HashSet::new();
+
+        for event in self.events.try_iter() {
+            match event {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    changed.extend(event.paths);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Error while watching the assets for changes: {}", e),
+            }
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,206 @@
+use sdl2::{
+    image::{ImageRWops, SaveSurface},
+    pixels::{Color, PixelFormatEnum},
+    rwops::RWops,
+    surface::Surface,
+};
+
+use crate::{
+    prelude::{MgiResult, Rect},
+    vfs::Vfs,
+};
+
+/// An image kept in memory on the CPU, stored as RGBA bytes row by row.
+///
+/// Images can be edited before being turned into a texture with
+/// `TextureManager::add_texture_from_image`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Creates a transparent image
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> MgiResult<Self> {
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(format!(
+                "A {}x{} image needs {} bytes of RGBA pixels, but got {}",
+                width,
+                height,
+                expected,
+                pixels.len()
+            )
+            .into());
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Loads an image file through the virtual filesystem (see `Context::vfs`), in any format
+    /// supported by SDL_image. An empty `Vfs::new()` reads the file from disk directly.
+    pub fn load_from(vfs: &Vfs, path: &str) -> MgiResult<Self> {
+        Self::from_bytes(&vfs.read(path)?)
+            .map_err(|e| format!("Couldn't load `{}`: {}", path, e).into())
+    }
+
+    /// Decodes an image file that is already in memory (e.g. embedded with `include_bytes!`)
+    pub fn from_bytes(bytes: &[u8]) -> MgiResult<Self> {
+        Self::from_surface(RWops::from_bytes(bytes)?.load()?)
+    }
+
+    /// Saves the image as a PNG file
+    pub fn save(&self, path: &str) -> MgiResult<()> {
+        let mut pixels = self.pixels.clone();
+        let surface = Surface::from_data(
+            &mut pixels,
+            self.width,
+            self.height,
+            self.width * 4,
+            PixelFormatEnum::RGBA32,
+        )?;
+
+        Ok(surface
+            .save(path)
+            .map_err(|e| format!("Couldn't save `{}`: {}", path, e))?)
+    }
+
+    fn from_surface(surface: Surface) -> MgiResult<Self> {
+        let surface = surface.convert_format(PixelFormatEnum::RGBA32)?;
+        let (width, height) = surface.size();
+        let pitch = surface.pitch() as usize;
+        let row_len = width as usize * 4;
+
+        // The rows of the surface may be padded
+        let pixels = surface.with_lock(|data| {
+            data.chunks(pitch)
+                .take(height as usize)
+                .flat_map(|row| &row[..row_len])
+                .copied()
+                .collect()
+        });
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    /// Returns `None` if the pixel is out of bounds
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let idx = self.index(x, y)?;
+        let p = &self.pixels[idx..idx + 4];
+
+        Some(Color::RGBA(p[0], p[1], p[2], p[3]))
+    }
+
+    /// Pixels that are out of bounds are ignored
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if let Some(idx) = self.index(x, y) {
+            self.pixels[idx..idx + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    /// Returns the part of the image inside `rect`, which must overlap the image
+    pub fn crop(&self, rect: Rect) -> MgiResult<Image> {
+        let rect = rect
+            .intersection(&Rect::new((0, 0), self.width, self.height))
+            .ok_or_else(|| {
+                format!(
+                    "{:?} is outside of the {}x{} image",
+                    rect, self.width, self.height
+                )
+            })?;
+
+        let row_len = self.width as usize * 4;
+        let (left, width) = (rect.x as usize * 4, rect.width as usize * 4);
+        let pixels = (rect.y as usize..rect.y as usize + rect.height as usize)
+            .flat_map(|y| &self.pixels[y * row_len + left..y * row_len + left + width])
+            .copied()
+            .collect();
+
+        Ok(Image {
+            width: rect.width,
+            height: rect.height,
+            pixels,
+        })
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        let width = self.width as usize;
+        if width == 0 {
+            return;
+        }
+
+        for row in self.pixels.chunks_exact_mut(width * 4) {
+            for x in 0..width / 2 {
+                let (left, right) = (x * 4, (width - 1 - x) * 4);
+                for channel in 0..4 {
+                    row.swap(left + channel, right + channel);
+                }
+            }
+        }
+    }
+
+    pub fn flip_vertical(&mut self) {
+        let row_len = self.width as usize * 4;
+        let height = self.height as usize;
+        for y in 0..height / 2 {
+            let (top, bottom) = self.pixels.split_at_mut((height - 1 - y) * row_len);
+            top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+        }
+    }
+
+    /// Replaces every pixel of exactly the color `from` with `to`
+    pub fn recolor(&mut self, from: Color, to: Color) {
+        self.map_pixels(|color| if color == from { to } else { color });
+    }
+
+    /// Replaces every pixel with the result of `f`
+    pub fn map_pixels(&mut self, mut f: impl FnMut(Color) -> Color) {
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            let color = f(Color::RGBA(pixel[0], pixel[1], pixel[2], pixel[3]));
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height)
+            .then_some((y as usize * self.width as usize + x as usize) * 4)
+    }
+}
@@ -1,19 +1,64 @@
+pub mod asset_group;
+pub mod atlas;
+pub mod blend;
 pub mod context;
+pub mod draw_context;
 pub mod drawable;
 pub mod game_builder;
+pub mod handle;
+pub mod image;
+pub mod loader;
+pub mod mesh;
+pub mod missing_asset;
+pub mod nine_slice;
+pub mod particles;
+pub mod path;
+pub mod pixel_buffer;
+pub mod rect;
+pub mod render_target;
+pub mod scene;
+pub mod sprite;
 pub mod texture_manager;
 pub mod tilemap;
+pub mod transform;
+pub mod tween;
 pub mod utils;
+pub mod vfs;
 
+#[cfg(feature = "hot-reload")]
+pub(crate) mod hot_reload;
+pub(crate) mod manifest;
+pub(crate) mod pack;
 pub(crate) mod resource_manager;
+pub(crate) mod svg;
 
 pub mod prelude {
+    pub use crate::asset_group::AssetGroup;
+    pub use crate::atlas::AtlasOptions;
+    pub use crate::blend::*;
     pub use crate::context::Context;
-    pub use crate::drawable::Rectangle;
+    pub use crate::draw_context::DrawContext;
+    pub use crate::drawable::{Drawable, Rectangle};
     pub use crate::game_builder::*;
+    pub use crate::handle::{AssetKey, Handle};
+    pub use crate::image::Image;
+    pub use crate::loader::{AssetStatus, LoadingState};
+    pub use crate::mesh::*;
+    pub use crate::missing_asset::MissingAssetPolicy;
+    pub use crate::nine_slice::*;
+    pub use crate::particles::{Curve, ParticleEmitter};
+    pub use crate::path::*;
+    pub use crate::pixel_buffer::PixelBuffer;
+    pub use crate::rect::Rect;
+    pub use crate::render_target::RenderTarget;
+    pub use crate::scene::*;
+    pub use crate::sprite::Sprite;
     pub use crate::texture_manager::*;
     pub use crate::tilemap::*;
+    pub use crate::transform::*;
+    pub use crate::tween::*;
     pub use crate::utils::*;
+    pub use crate::vfs::Vfs;
 
     pub use sdl2::{keyboard::Keycode, pixels::Color};
 }
@@ -0,0 +1,229 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    handle::Handle,
+    image::Image,
+    prelude::MgiResult,
+    svg,
+    texture_manager::{Texture, TextureSource},
+    vfs::Vfs,
+};
+
+/// How far along an asset loaded in the background is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetStatus {
+    Loading,
+    Loaded,
+
+    /// The asset couldn't be loaded, with the reason why
+    Failed(String),
+}
+
+/// The assets loaded in the background, e.g. to show a loading screen.
+///
+/// Once every asset is finished, the next asset queued starts a new list.
+#[derive(Debug, Default)]
+pub struct LoadingState {
+    assets: Vec<(String, AssetStatus)>,
+}
+
+impl LoadingState {
+    /// The fraction of the assets that are finished (loaded or failed), from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.assets.is_empty() {
+            return 1.;
+        }
+
+        let finished = self
+            .assets
+            .iter()
+            .filter(|(_, status)| *status != AssetStatus::Loading)
+            .count();
+
+        finished as f32 / self.assets.len() as f32
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.assets
+            .iter()
+            .all(|(_, status)| *status != AssetStatus::Loading)
+    }
+
+    pub fn status(&self, name: &str) -> Option<&AssetStatus> {
+        self.assets
+            .iter()
+            .rev()
+            .find(|(asset, _)| asset == name)
+            .map(|(_, status)| status)
+    }
+
+    /// Every asset in the list, in the order they were queued
+    pub fn assets(&self) -> impl Iterator<Item = (&str, &AssetStatus)> {
+        self.assets
+            .iter()
+            .map(|(name, status)| (name.as_str(), status))
+    }
+
+    /// The assets that failed to load, with the reason why
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.assets
+            .iter()
+            .filter_map(|(name, status)| match status {
+                AssetStatus::Failed(error) => Some((name.as_str(), error.as_str())),
+                _ => None,
+            })
+    }
+}
+
+struct Job {
+    index: usize,
+    handle: Handle<Texture>,
+    path: String,
+    source: Option<TextureSource>,
+    svg_scale: f32,
+    vfs: Vfs,
+}
+
+/// A texture decoded by a worker, which still has to be uploaded on the main thread
+pub(crate) struct Decoded {
+    pub(crate) index: usize,
+    pub(crate) handle: Handle<Texture>,
+    pub(crate) path: String,
+    pub(crate) source: Option<TextureSource>,
+    pub(crate) image: Result<Image, String>,
+}
+
+/// Decodes images on worker threads. Textures can only be created on the main thread, so the
+/// decoded images are uploaded by `Context` every frame.
+pub(crate) struct Loader {
+    state: LoadingState,
+
+    /// Created with the workers the first time an asset is queued
+    jobs: Option<Sender<Job>>,
+    results: Receiver<Decoded>,
+    results_sender: Sender<Decoded>,
+}
+
+impl Loader {
+    pub(crate) fn new() -> Self {
+        let (results_sender, results) = mpsc::channel();
+
+        Self {
+            state: LoadingState::default(),
+            jobs: None,
+            results,
+            results_sender,
+        }
+    }
+
+    pub(crate) fn state(&self) -> &LoadingState {
+        &self.state
+    }
+
+    pub(crate) fn queue(
+        &mut self,
+        name: &str,
+        handle: Handle<Texture>,
+        path: &str,
+        source: Option<TextureSource>,
+        svg_scale: f32,
+        vfs: Vfs,
+    ) {
+        if self.state.is_done() {
+            self.state.assets.clear();
+        }
+
+        let index = self.state.assets.len();
+        self.state.assets.push((name.into(), AssetStatus::Loading));
+
+        let results = &self.results_sender;
+        let sent = self
+            .jobs
+            .get_or_insert_with(|| spawn_workers(results))
+            .send(Job {
+                index,
+                handle,
+                path: path.into(),
+                source,
+                svg_scale,
+                vfs,
+            });
+
+        // Only happens if every worker panicked
+        if sent.is_err() {
+            self.finish(index, Err("The loading threads stopped".into()));
+        }
+    }
+
+    /// The images decoded since the last call
+    pub(crate) fn decoded(&self) -> Vec<Decoded> {
+        self.results.try_iter().collect()
+    }
+
+    pub(crate) fn finish(&mut self, index: usize, result: Result<(), String>) {
+        let (name, status) = &mut self.state.assets[index];
+        *status = match result {
+            Ok(()) => AssetStatus::Loaded,
+            Err(error) => AssetStatus::Failed(format!("Couldn't load `{}`: {}", name, error)),
+        };
+    }
+}
+
+fn spawn_workers(results: &Sender<Decoded>) -> Sender<Job> {
+    let (jobs, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let count = thread::available_parallelism().map_or(2, |n| n.get().min(4));
+
+    for _ in 0..count {
+        let receiver = Arc::clone(&receiver);
+        let results = results.clone();
+
+        thread::spawn(move || loop {
+            // The lock is released before decoding, so the workers decode in parallel
+            let job = match receiver.lock().unwrap().recv() {
+                Ok(job) => job,
+                Err(_) => break,
+            };
+
+            let image = decode(&job.vfs, &job.path, job.source.as_ref(), job.svg_scale);
+            let decoded = Decoded {
+                index: job.index,
+                handle: job.handle,
+                path: job.path,
+                source: job.source,
+                image: image.map_err(|e| e.to_string()),
+            };
+            if results.send(decoded).is_err() {
+                break;
+            }
+        });
+    }
+
+    jobs
+}
+
+fn decode(
+    vfs: &Vfs,
+    path: &str,
+    source: Option<&TextureSource>,
+    svg_scale: f32,
+) -> MgiResult<Image> {
+    match source {
+        Some(TextureSource::Svg(size)) => {
+            let ((width, height), pixels) =
+                svg::rasterize(path, &vfs.read(path)?, *size, svg_scale)?;
+            Image::from_rgba(width, height, pixels)
+        }
+        Some(TextureSource::Bytes(bytes)) => Image::from_bytes(bytes),
+        Some(TextureSource::Rgba((width, height), pixels)) => {
+            Image::from_rgba(*width, *height, pixels.clone())
+        }
+        None => Image::from_bytes(&vfs.read(path)?),
+    }
+}
@@ -0,0 +1,407 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Deserialize;
+
+#[cfg(feature = "hot-reload")]
+use std::rc::Rc;
+
+use crate::{
+    atlas::AtlasOptions,
+    handle::Assets,
+    prelude::MgiResult,
+    svg,
+    texture_manager::TextureManager,
+    tilemap::{TileMap, TileSet},
+    vfs::Vfs,
+};
+#[cfg(feature = "hot-reload")]
+use crate::{context::Context, texture_manager::TextureSource};
+
+/// The assets listed in a manifest file, see `GameBuilder::load_manifest`.
+///
+/// mgi has no fonts or sounds, so manifests only list textures and tilemaps (and how the
+/// textures are packed into atlases).
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Manifest {
+    /// Used in error messages
+    #[serde(skip)]
+    path: String,
+
+    #[serde(default)]
+    textures: BTreeMap<String, ManifestTexture>,
+
+    #[serde(default)]
+    tilemaps: BTreeMap<String, ManifestTileMap>,
+
+    atlas: Option<ManifestAtlas>,
+}
+
+/// Either just the path, or a table with the options
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TextureEntry {
+    Path(String),
+    Options(TextureOptions),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TextureOptions {
+    path: String,
+
+    /// The size SVGs are rasterized at
+    size: Option<(u32, u32)>,
+}
+
+#[derive(Deserialize)]
+#[serde(from = "TextureEntry")]
+struct ManifestTexture {
+    path: String,
+    size: Option<(u32, u32)>,
+}
+
+impl From<TextureEntry> for ManifestTexture {
+    fn from(entry: TextureEntry) -> Self {
+        match entry {
+            TextureEntry::Path(path) => Self { path, size: None },
+            TextureEntry::Options(options) => Self {
+                path: options.path,
+                size: options.size,
+            },
+        }
+    }
+}
+
+/// The options of `TextureManager::set_atlas`, the missing ones keep their default
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestAtlas {
+    page_size: Option<(u32, u32)>,
+    padding: Option<u32>,
+    extrude: Option<u32>,
+
+    /// A directory on disk, relative to the working directory rather than the manifest
+    dump: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestTileMap {
+    tile_size: (usize, usize),
+
+    /// The names of the textures of the tiles
+    tiles: Vec<String>,
+
+    /// Rows of indices into `tiles`, from top to bottom
+    grid: Vec<Vec<usize>>,
+}
+
+impl Manifest {
+    pub(crate) fn load(vfs: &Vfs, path: &str) -> MgiResult<Self> {
+        let text = String::from_utf8(vfs.read(path)?)
+            .map_err(|e| format!("Couldn't read the manifest `{}`: {}", path, e))?;
+
+        Self::parse(path, &text)
+    }
+
+    fn parse(path: &str, text: &str) -> MgiResult<Self> {
+        let mut manifest: Manifest =
+            toml::from_str(text).map_err(|e| format!("Invalid manifest `{}`: {}", path, e))?;
+        manifest.path = path.into();
+
+        // Paths are relative to the manifest
+        let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+        for texture in manifest.textures.values_mut() {
+            texture.path = directory.join(&texture.path).to_string_lossy().into();
+        }
+
+        Ok(manifest)
+    }
+
+    /// Checks every entry up front, reporting all the bad ones. The tiles of the tilemaps are
+    /// checked by `validate_tiles`, since they can use textures that aren't in the manifest.
+    pub(crate) fn validate(&self, vfs: &Vfs) -> MgiResult<()> {
+        let mut errors = Vec::new();
+
+        for (name, texture) in &self.textures {
+            if !vfs.exists(&texture.path) {
+                errors.push(format!(
+                    "textures.{}: `{}` does not exist",
+                    name, texture.path
+                ));
+            }
+
+            match texture.size {
+                Some(_) if !svg::is_svg(&texture.path) => errors.push(format!(
+                    "textures.{}: a size can only be given to SVG textures",
+                    name
+                )),
+                Some((0, _) | (_, 0)) => {
+                    errors.push(format!("textures.{}: the size must not be zero", name))
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(atlas) = &self.atlas {
+            if let Some((0, _) | (_, 0)) = atlas.page_size {
+                errors.push("atlas: the page size must not be zero".into());
+            }
+        }
+
+        for (name, tilemap) in &self.tilemaps {
+            if let (0, _) | (_, 0) = tilemap.tile_size {
+                errors.push(format!("tilemaps.{}: the tile size must not be zero", name));
+            }
+
+            let width = tilemap.grid.first().map_or(0, Vec::len);
+            if width == 0 {
+                errors.push(format!("tilemaps.{}: the grid is empty", name));
+            }
+
+            for (y, row) in tilemap.grid.iter().enumerate() {
+                if row.len() != width {
+                    errors.push(format!(
+                        "tilemaps.{}: row {} has {} tiles instead of {}",
+                        name,
+                        y,
+                        row.len(),
+                        width
+                    ));
+                }
+                if let Some(tile) = row.iter().find(|tile| **tile >= tilemap.tiles.len()) {
+                    errors.push(format!(
+                        "tilemaps.{}: row {} uses tile {}, but there are only {} tiles",
+                        name,
+                        y,
+                        tile,
+                        tilemap.tiles.len()
+                    ));
+                }
+            }
+        }
+
+        self.report(errors)
+    }
+
+    /// Checks that the tiles of the tilemaps are textures, either of the manifest or the ones
+    /// `is_texture` accepts
+    pub(crate) fn validate_tiles(&self, is_texture: impl Fn(&str) -> bool) -> MgiResult<()> {
+        let mut errors = Vec::new();
+
+        for (name, tilemap) in &self.tilemaps {
+            for tile in &tilemap.tiles {
+                if !self.textures.contains_key(tile) && !is_texture(tile) {
+                    errors.push(format!(
+                        "tilemaps.{}: there is no texture named `{}`",
+                        name, tile
+                    ));
+                }
+            }
+        }
+
+        self.report(errors)
+    }
+
+    fn report(&self, errors: Vec<String>) -> MgiResult<()> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid manifest `{}`:\n{}", self.path, errors.join("\n")).into())
+        }
+    }
+
+    /// Adds the textures to the texture manager, and sets its atlas options if the manifest has
+    /// an `[atlas]` section
+    pub(crate) fn register_textures(&self, texture_manager: &mut TextureManager) {
+        for (name, texture) in &self.textures {
+            match texture.size {
+                Some(size) => texture_manager.add_svg_texture(name, &texture.path, size),
+                None => texture_manager.add_texture(name, &texture.path),
+            };
+        }
+
+        if let Some(atlas) = &self.atlas {
+            texture_manager.set_atlas(atlas.options());
+        }
+    }
+
+    pub(crate) fn register_tilemaps(self, tilemaps: &mut Assets<TileMap>) {
+        for (name, tilemap) in self.tilemaps {
+            tilemaps.insert(&name, tilemap.into_tilemap());
+        }
+    }
+
+    /// Loads the assets while the game is running, replacing the ones with the same names in
+    /// place. Textures are all loaded before anything is replaced, so a texture that fails to load
+    /// leaves every asset as it was.
+    ///
+    /// The `[atlas]` section is ignored, since textures are only packed when the game starts.
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn reload(self, ctx: &mut Context) -> MgiResult<()> {
+        let texture_manager = Rc::clone(ctx.texture_manager());
+        let mut texture_manager = texture_manager.borrow_mut();
+
+        let mut textures = Vec::with_capacity(self.textures.len());
+        for (name, texture) in self.textures {
+            let source = texture.size.map(|size| TextureSource::Svg(Some(size)));
+            match texture_manager.decode_texture(&name, &texture.path, source.clone()) {
+                Ok(raw) => textures.push((name, texture.path, source, raw)),
+                Err(e) => {
+                    for (_, _, _, raw) in textures {
+                        unsafe { raw.destroy() };
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        for (name, path, source, raw) in textures {
+            texture_manager.replace_texture(&name, &path, source, raw);
+        }
+        drop(texture_manager);
+
+        for (name, tilemap) in self.tilemaps {
+            ctx.load_tilemap(&name, tilemap.into_tilemap());
+        }
+
+        Ok(())
+    }
+}
+
+impl ManifestAtlas {
+    fn options(&self) -> AtlasOptions {
+        let mut options = AtlasOptions::new();
+        if let Some((width, height)) = self.page_size {
+            options = options.page_size(width, height);
+        }
+        if let Some(padding) = self.padding {
+            options = options.padding(padding);
+        }
+        if let Some(extrude) = self.extrude {
+            options = options.extrude(extrude);
+        }
+        if let Some(directory) = &self.dump {
+            options = options.dump(directory);
+        }
+
+        options
+    }
+}
+
+impl ManifestTileMap {
+    fn into_tilemap(self) -> TileMap {
+        let mut tileset = TileSet::new();
+        for tile in &self.tiles {
+            tileset.add_tile_type(tile);
+        }
+
+        // The tiles are placed with `x` going up to the first dimension of the tilemap
+        let grid = self.grid;
+        let mut tilemap = TileMap::new(grid[0].len(), grid.len(), self.tile_size);
+        tilemap.add_tileset(tileset);
+        tilemap.add_tile_placement_fn(Box::new(move |x, y| (0, grid[y as usize][x as usize])));
+
+        tilemap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "examples/assets/test.toml";
+
+    fn errors(text: &str) -> Vec<String> {
+        match Manifest::parse(PATH, text).unwrap().validate(&Vfs::new()) {
+            Ok(()) => Vec::new(),
+            Err(e) => e.to_string().lines().skip(1).map(String::from).collect(),
+        }
+    }
+
+    #[test]
+    fn example_manifest_is_valid() {
+        let manifest = Manifest::load(&Vfs::new(), "examples/assets/manifest.toml").unwrap();
+        manifest.validate(&Vfs::new()).unwrap();
+        manifest.validate_tiles(|_| false).unwrap();
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_manifest() {
+        let manifest = Manifest::parse(PATH, "[textures]\nbg = \"bg.png\"").unwrap();
+        assert_eq!(manifest.textures["bg"].path, "examples/assets/bg.png");
+    }
+
+    #[test]
+    fn reports_every_bad_entry() {
+        let errors = errors(
+            r#"
+            [textures]
+            missing = "missing.png"
+            sized = { path = "bg.png", size = [32, 32] }
+            empty = { path = "star.svg", size = [0, 32] }
+
+            [tilemaps.flat]
+            tile_size = [32, 0]
+            tiles = ["bg"]
+            grid = [[0, 0], [0], [0, 1]]
+
+            [tilemaps.empty]
+            tile_size = [32, 32]
+            tiles = []
+            grid = []
+
+            [atlas]
+            page_size = [0, 0]
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            [
+                "textures.empty: the size must not be zero",
+                "textures.missing: `examples/assets/missing.png` does not exist",
+                "textures.sized: a size can only be given to SVG textures",
+                "atlas: the page size must not be zero",
+                "tilemaps.empty: the grid is empty",
+                "tilemaps.flat: the tile size must not be zero",
+                "tilemaps.flat: row 1 has 1 tiles instead of 2",
+                "tilemaps.flat: row 2 uses tile 1, but there are only 1 tiles",
+            ]
+        );
+    }
+
+    #[test]
+    fn tiles_can_be_textures_outside_the_manifest() {
+        let manifest = Manifest::parse(
+            PATH,
+            r#"
+            [textures]
+            ground = "tileset/ground.png"
+
+            [tilemaps.world]
+            tile_size = [32, 32]
+            tiles = ["ground", "water"]
+            grid = [[0, 1]]
+            "#,
+        )
+        .unwrap();
+
+        manifest.validate_tiles(|name| name == "water").unwrap();
+        let e = manifest.validate_tiles(|_| false).unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("tilemaps.world: there is no texture named `water`"));
+    }
+
+    #[test]
+    fn atlas_options() {
+        let manifest = Manifest::parse(PATH, "[atlas]\npadding = 4\ndump = \"dump\"").unwrap();
+        assert_eq!(
+            manifest.atlas.unwrap().options(),
+            AtlasOptions::new().padding(4).dump("dump")
+        );
+
+        assert!(Manifest::parse(PATH, "[atlas]\nsize = 4").is_err());
+    }
+}
@@ -0,0 +1,460 @@
+use std::{
+    ffi::{c_void, CStr},
+    mem,
+    os::raw::c_int,
+    ptr,
+    sync::OnceLock,
+};
+
+use sdl2::{
+    pixels::Color,
+    sys::{SDL_LoadFunction, SDL_LoadObject, SDL_Renderer, SDL_Texture},
+};
+
+use crate::{
+    blend::{with_canvas_blend_mode, with_texture_blend_mode},
+    drawable::Drawable,
+    prelude::*,
+    scene::SceneDrawable,
+    transform::Matrix2D,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SdlFPoint {
+    x: f32,
+    y: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SdlColor {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SdlVertex {
+    position: SdlFPoint,
+    color: SdlColor,
+    tex_coord: SdlFPoint,
+}
+
+type RenderGeometry = unsafe extern "C" fn(
+    renderer: *mut SDL_Renderer,
+    texture: *mut SDL_Texture,
+    vertices: *const SdlVertex,
+    num_vertices: c_int,
+    indices: *const c_int,
+    num_indices: c_int,
+) -> c_int;
+
+/// `SDL_RenderGeometry` was added in SDL 2.0.18, after the bindings used by `sdl2` were
+/// generated. It's looked up when the first mesh is drawn rather than linked, so that games that
+/// don't draw meshes still run with older versions of SDL.
+fn sdl_render_geometry() -> MgiResult<RenderGeometry> {
+    static FUNCTION: OnceLock<Option<RenderGeometry>> = OnceLock::new();
+
+    let function = FUNCTION.get_or_init(|| {
+        // The symbols of the process itself (which include SDL's, except on Windows), then SDL's
+        // library, which is already loaded
+        let libraries: [Option<&CStr>; 5] = [
+            None,
+            Some(c"SDL2.dll"),
+            Some(c"libSDL2-2.0.so.0"),
+            Some(c"libSDL2-2.0.0.dylib"),
+            Some(c"libSDL2.so"),
+        ];
+
+        libraries.iter().find_map(|library| unsafe {
+            let handle = SDL_LoadObject(library.map_or(ptr::null(), |name| name.as_ptr()));
+            if handle.is_null() {
+                return None;
+            }
+
+            let function = SDL_LoadFunction(handle, c"SDL_RenderGeometry".as_ptr());
+            (!function.is_null())
+                .then(|| mem::transmute::<*mut c_void, RenderGeometry>(function.cast()))
+        })
+    });
+
+    function.ok_or_else(|| {
+        format!(
+            "Meshes require SDL 2.0.18 or newer, but SDL {} is used",
+            sdl2::version::version()
+        )
+        .into()
+    })
+}
+
+/// A vertex of a `Mesh`
+#[derive(Debug, Clone, Copy)]
+pub struct Vertex {
+    pub position: Vec2f,
+    pub color: Color,
+
+    /// The normalized (`0.0..=1.0`) texture coordinates of the vertex
+    pub uv: Vec2f,
+}
+
+impl Vertex {
+    pub fn new(position: impl Into<Vec2f>, color: Color, uv: impl Into<Vec2f>) -> Self {
+        Self {
+            position: position.into(),
+            color,
+            uv: uv.into(),
+        }
+    }
+}
+
+/// Triangles with per-vertex colors, optionally textured with a texture from the
+/// `TextureManager`.
+///
+/// Every 3 indices (or every 3 vertices if there are no indices) form a triangle.
+///
+/// NOTE: Meshes are drawn with `SDL_RenderGeometry`, which requires SDL 2.0.18 or newer (drawing
+/// a mesh with an older version of SDL returns an error)
+#[derive(Clone)]
+pub struct Mesh {
+    vertices: Vec<Vertex>,
+    indices: Option<Vec<u32>>,
+    texture: Option<AssetKey<Texture>>,
+    blend_mode: Option<BlendMode>,
+}
+
+impl Mesh {
+    pub fn new(vertices: Vec<Vertex>) -> Self {
+        Self {
+            vertices,
+            indices: None,
+            texture: None,
+            blend_mode: None,
+        }
+    }
+
+    /// Creates a single colored mesh filling the polygon, see `triangulate`
+    pub fn from_polygon(points: &[Vec2f], color: Color) -> MgiResult<Self> {
+        let vertices = points
+            .iter()
+            .map(|point| Vertex::new(*point, color, Vec2f::ZERO))
+            .collect();
+
+        Ok(Self::new(vertices).indices(triangulate(points)?))
+    }
+
+    pub fn indices(mut self, indices: Vec<u32>) -> Self {
+        self.indices = Some(indices);
+        self
+    }
+
+    pub fn texture(mut self, texture: impl Into<AssetKey<Texture>>) -> Self {
+        self.texture = Some(texture.into());
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    pub fn vertices_mut(&mut self) -> &mut [Vertex] {
+        &mut self.vertices
+    }
+
+    /// Draws the mesh, with its vertex positions transformed and vertex colors multiplied by `tint`.
+    ///
+    /// `blend_mode` is used if the mesh doesn't have its own blend mode.
+    pub(crate) fn draw_with(
+        &self,
+        ctx: &Context,
+        transform: &Matrix2D,
+        tint: Color,
+        blend_mode: Option<BlendMode>,
+    ) -> MgiResult<()> {
+        let blend_mode = self.blend_mode.or(blend_mode);
+        let mut vertices: Vec<SdlVertex> = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let position = transform.transform_point(vertex.position);
+                let channel = |a: u8, b: u8| (a as u16 * b as u16 / 255) as u8;
+
+                SdlVertex {
+                    position: SdlFPoint {
+                        x: position.x,
+                        y: position.y,
+                    },
+                    color: SdlColor {
+                        r: channel(vertex.color.r, tint.r),
+                        g: channel(vertex.color.g, tint.g),
+                        b: channel(vertex.color.b, tint.b),
+                        a: channel(vertex.color.a, tint.a),
+                    },
+                    tex_coord: SdlFPoint {
+                        x: vertex.uv.x,
+                        y: vertex.uv.y,
+                    },
+                }
+            })
+            .collect();
+
+        let indices: Option<Vec<c_int>> = self
+            .indices
+            .as_ref()
+            .map(|indices| indices.iter().map(|idx| *idx as c_int).collect());
+        if let Some(idx) = indices
+            .iter()
+            .flatten()
+            .find(|idx| **idx as usize >= vertices.len())
+        {
+            return Err(format!("The mesh index {} is out of bounds", idx).into());
+        }
+
+        if let Some(texture) = &self.texture {
+            let region = match ctx.resolve_texture(texture)? {
+                Some(region) => region,
+                None => return Ok(()),
+            };
+
+            // The texture coordinates are relative to the texture, which may be a region of an
+            // atlas
+            if region.rect.is_some() {
+                for vertex in &mut vertices {
+                    let uv = region.uv(Vec2f::new(vertex.tex_coord.x, vertex.tex_coord.y));
+                    vertex.tex_coord = SdlFPoint { x: uv.x, y: uv.y };
+                }
+            }
+
+            let renderer = ctx.canvas().borrow().raw();
+            let mut raw = region.raw.borrow_mut();
+            with_texture_blend_mode(&mut raw, blend_mode, |raw| {
+                render_geometry(renderer, raw.raw(), &vertices, indices.as_deref())
+            })
+        } else {
+            // Untextured meshes are alpha blended by default, so vertex alphas are respected
+            let blend_mode = blend_mode.or(Some(BlendMode::Alpha));
+
+            let canvas = ctx.canvas();
+            let mut canvas = canvas.borrow_mut();
+            with_canvas_blend_mode(&mut canvas, blend_mode, |canvas| {
+                render_geometry(canvas.raw(), ptr::null_mut(), &vertices, indices.as_deref())
+            })
+        }
+    }
+}
+
+fn render_geometry(
+    renderer: *mut SDL_Renderer,
+    texture: *mut SDL_Texture,
+    vertices: &[SdlVertex],
+    indices: Option<&[c_int]>,
+) -> MgiResult<()> {
+    let (indices_ptr, num_indices) = match indices {
+        Some(indices) => (indices.as_ptr(), indices.len() as c_int),
+        None => (ptr::null(), 0),
+    };
+
+    let render_geometry = sdl_render_geometry()?;
+    let res = unsafe {
+        render_geometry(
+            renderer,
+            texture,
+            vertices.as_ptr(),
+            vertices.len() as c_int,
+            indices_ptr,
+            num_indices,
+        )
+    };
+    if res != 0 {
+        return Err(sdl2::get_error().into());
+    }
+
+    Ok(())
+}
+
+impl Drawable for Mesh {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        self.draw_with(ctx, &Matrix2D::IDENTITY, Color::WHITE, None)
+    }
+}
+
+impl SceneDrawable for Mesh {
+    fn draw_transformed(&mut self, ctx: &Context, transform: &Matrix2D) -> MgiResult<()> {
+        self.draw_with(ctx, transform, Color::WHITE, None)
+    }
+}
+
+/// Returns twice the signed area of the polygon (positive if the points are clockwise on the
+/// screen, where y points down)
+fn signed_area(points: &[Vec2f]) -> f32 {
+    (0..points.len())
+        .map(|i| points[i].cross(points[(i + 1) % points.len()]))
+        .sum()
+}
+
+fn in_triangle(p: Vec2f, a: Vec2f, b: Vec2f, c: Vec2f) -> bool {
+    let d1 = (b - a).cross(p - a);
+    let d2 = (c - b).cross(p - b);
+    let d3 = (a - c).cross(p - c);
+
+    let has_neg = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_pos = d1 > 0. || d2 > 0. || d3 > 0.;
+
+    !(has_neg && has_pos)
+}
+
+/// Splits a simple (non self-intersecting) polygon into triangles, using ear clipping.
+///
+/// The points can be in either winding order, and the returned indices index into `points`.
+/// Collinear points don't add any triangle, and self-intersecting polygons (which can't always be
+/// split into triangles) are an error.
+pub fn triangulate(points: &[Vec2f]) -> MgiResult<Vec<u32>> {
+    if points.len() < 3 {
+        return Ok(Vec::new());
+    }
+
+    // Ears are found assuming a positive winding
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0. {
+        remaining.reverse();
+    }
+
+    let corner = |remaining: &[usize], i: usize| {
+        let len = remaining.len();
+        (
+            remaining[(i + len - 1) % len],
+            remaining[i],
+            remaining[(i + 1) % len],
+        )
+    };
+    let turn = |(prev, cur, next): (usize, usize, usize)| {
+        (points[cur] - points[prev]).cross(points[next] - points[cur])
+    };
+
+    let mut indices = Vec::with_capacity((points.len() - 2) * 3);
+    while remaining.len() > 3 {
+        let ear = (0..remaining.len()).find(|&i| {
+            let (prev, cur, next) = corner(&remaining, i);
+            let (a, b, c) = (points[prev], points[cur], points[next]);
+
+            // The ear must be convex, and not contain any of the other points
+            turn((prev, cur, next)) > 0.
+                && !remaining.iter().any(|&other| {
+                    other != prev
+                        && other != cur
+                        && other != next
+                        && in_triangle(points[other], a, b, c)
+                })
+        });
+
+        if let Some(ear) = ear {
+            let (prev, cur, next) = corner(&remaining, ear);
+            indices.extend([prev as u32, cur as u32, next as u32]);
+            remaining.remove(ear);
+            continue;
+        }
+
+        // Without ears, only collinear points (which cover no area) can be removed
+        match (0..remaining.len()).find(|&i| turn(corner(&remaining, i)) == 0.) {
+            Some(collinear) => {
+                remaining.remove(collinear);
+            }
+            None => {
+                return Err(format!(
+                    "Couldn't triangulate the polygon of {} points: it intersects itself",
+                    points.len()
+                )
+                .into())
+            }
+        }
+    }
+
+    if turn(corner(&remaining, 0)) != 0. {
+        indices.extend(remaining.iter().map(|idx| *idx as u32));
+    }
+
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(points: &[(f32, f32)]) -> Vec<Vec2f> {
+        points.iter().map(|(x, y)| Vec2f::new(*x, *y)).collect()
+    }
+
+    /// The total area of the triangles, checking that they all have the same winding
+    fn area(points: &[Vec2f], indices: &[u32]) -> f32 {
+        assert_eq!(indices.len() % 3, 0);
+
+        indices
+            .chunks(3)
+            .map(|triangle| {
+                let triangle: Vec<_> = triangle.iter().map(|idx| points[*idx as usize]).collect();
+                let area = signed_area(&triangle);
+                assert!(area > 0., "{:?} is flipped or empty", triangle);
+                area / 2.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn triangulates_convex_polygons() {
+        for points in [
+            points(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]),
+            points(&[(0., 0.), (0., 10.), (10., 10.), (10., 0.)]),
+        ] {
+            let indices = triangulate(&points).unwrap();
+            assert_eq!(indices.len(), 6);
+            assert_eq!(area(&points, &indices), 100.);
+        }
+    }
+
+    #[test]
+    fn triangulates_concave_polygons() {
+        // An L shape and an arrow head
+        let l = points(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 5.),
+            (5., 5.),
+            (5., 10.),
+            (0., 10.),
+        ]);
+        let indices = triangulate(&l).unwrap();
+        assert_eq!(indices.len(), 12);
+        assert_eq!(area(&l, &indices), 75.);
+
+        let arrow = points(&[(0., 0.), (10., 5.), (0., 10.), (3., 5.)]);
+        let indices = triangulate(&arrow).unwrap();
+        assert_eq!(indices.len(), 6);
+        assert_eq!(area(&arrow, &indices), 35.);
+    }
+
+    #[test]
+    fn skips_collinear_points() {
+        let square = points(&[(0., 0.), (5., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        assert_eq!(area(&square, &triangulate(&square).unwrap()), 100.);
+
+        let repeated = points(&[(0., 0.), (10., 0.), (10., 0.), (10., 10.)]);
+        assert_eq!(area(&repeated, &triangulate(&repeated).unwrap()), 50.);
+
+        let line = points(&[(0., 0.), (5., 0.), (10., 0.), (2., 0.)]);
+        assert_eq!(triangulate(&line).unwrap(), Vec::<u32>::new());
+        assert_eq!(triangulate(&line[..2]).unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn self_intersecting_polygons_are_an_error() {
+        let crossed = points(&[(0., 8.), (5., 7.), (6., 7.), (7., 10.), (0., 0.)]);
+        assert!(triangulate(&crossed).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cell::RefCell, collections::HashSet, error::Error, rc::Rc};
+
+use sdl2::render::Texture as TextureRaw;
+
+use crate::{prelude::MgiResult, texture_manager::TextureRegion};
+
+/// What happens when an asset that doesn't exist, or that failed to load, is drawn.
+///
+/// Textures that are still being loaded in the background aren't missing: they are not drawn
+/// until they are loaded, whatever the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingAssetPolicy {
+    /// Drawing returns an error naming the asset
+    Strict,
+
+    /// A warning naming the asset is logged the first time it is drawn. Textures (including the
+    /// tiles of tilemaps) are replaced by a magenta and black checkerboard, and other assets are
+    /// not drawn.
+    WarnOnce,
+
+    /// The asset is not drawn, without any warning
+    #[default]
+    Silent,
+}
+
+/// Applies the `MissingAssetPolicy`
+pub(crate) struct MissingAssets {
+    pub(crate) policy: MissingAssetPolicy,
+
+    /// The errors that were already logged, so each one is only logged once
+    warned: RefCell<HashSet<String>>,
+
+    /// Created when the game starts
+    pub(crate) placeholder: Option<Rc<RefCell<TextureRaw>>>,
+}
+
+impl MissingAssets {
+    pub(crate) fn new() -> Self {
+        Self {
+            policy: MissingAssetPolicy::default(),
+            warned: RefCell::new(HashSet::new()),
+            placeholder: None,
+        }
+    }
+
+    /// Returns the error of the missing asset if the policy is strict
+    pub(crate) fn report(&self, error: Box<dyn Error>) -> MgiResult<()> {
+        match self.policy {
+            MissingAssetPolicy::Strict => Err(error),
+            MissingAssetPolicy::WarnOnce => {
+                let message = error.to_string();
+                if self.warned.borrow_mut().insert(message.clone()) {
+                    eprintln!("Warning: {}", message);
+                }
+                Ok(())
+            }
+            MissingAssetPolicy::Silent => Ok(()),
+        }
+    }
+
+    /// The texture to draw, which is the placeholder when it is missing and the policy is
+    /// warn-once (`None` means nothing should be drawn)
+    pub(crate) fn texture(
+        &self,
+        texture: MgiResult<TextureRegion>,
+    ) -> MgiResult<Option<TextureRegion>> {
+        match texture {
+            Ok(texture) => Ok(Some(texture)),
+            Err(e) => {
+                self.report(e)?;
+                Ok(match self.policy {
+                    MissingAssetPolicy::WarnOnce => {
+                        self.placeholder.clone().map(TextureRegion::whole)
+                    }
+                    _ => None,
+                })
+            }
+        }
+    }
+}
+
+/// A 16x16 checkerboard of 8x8 magenta and black squares, as RGBA pixels
+pub(crate) fn placeholder_pixels() -> Vec<u8> {
+    (0..16 * 16)
+        .flat_map(|i| {
+            let (x, y) = (i % 16, i / 16);
+            if (x / 8 + y / 8) % 2 == 0 {
+                [255, 0, 255, 255]
+            } else {
+                [0, 0, 0, 255]
+            }
+        })
+        .collect()
+}
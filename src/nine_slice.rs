@@ -0,0 +1,225 @@
+use sdl2::{
+    pixels::Color,
+    rect::Rect as SdlRect,
+    render::{Canvas, Texture as TextureRaw},
+    video::Window,
+};
+
+use crate::{
+    blend::with_texture_blend_mode, drawable::Drawable, prelude::*, texture_manager::with_tint,
+};
+
+/// How the edges and center of a `NineSlice` are filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceMode {
+    Stretch,
+    Tile,
+}
+
+/// The size of each border of a `NineSlice`, in texture pixels
+#[derive(Debug, Clone, Copy)]
+pub struct Insets {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl Insets {
+    pub fn new(left: u32, top: u32, right: u32, bottom: u32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn uniform(size: u32) -> Self {
+        Self::new(size, size, size, size)
+    }
+}
+
+/// A texture split into 9 regions, so it can be drawn at any size without stretching its borders.
+///
+/// The corners are always drawn at their original size, while the edges and center are either
+/// stretched or tiled to fill the destination.
+#[derive(Clone)]
+pub struct NineSlice {
+    texture: AssetKey<Texture>,
+    insets: Insets,
+    dest: Rect,
+    edge_mode: SliceMode,
+    center_mode: SliceMode,
+    color_tint: Option<Color>,
+    alpha_tint: Option<f32>,
+    blend_mode: Option<BlendMode>,
+}
+
+impl NineSlice {
+    pub fn new(texture: impl Into<AssetKey<Texture>>, insets: Insets, dest: Rect) -> Self {
+        Self {
+            texture: texture.into(),
+            insets,
+            dest,
+            edge_mode: SliceMode::Stretch,
+            center_mode: SliceMode::Stretch,
+            color_tint: None,
+            alpha_tint: None,
+            blend_mode: None,
+        }
+    }
+
+    pub fn edge_mode(mut self, mode: SliceMode) -> Self {
+        self.edge_mode = mode;
+        self
+    }
+
+    pub fn center_mode(mut self, mode: SliceMode) -> Self {
+        self.center_mode = mode;
+        self
+    }
+
+    pub fn color_tint(mut self, color: Color) -> Self {
+        self.color_tint = Some(color);
+        self
+    }
+
+    /// Alpha in the range `0.0..=1.0`
+    pub fn alpha_tint(mut self, alpha: f32) -> Self {
+        self.alpha_tint = Some(alpha);
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+}
+
+/// Splits a length into its 3 slices, returning the `(offset, size)` of each one.
+///
+/// If the length is smaller than both borders, the borders are shrunk proportionally.
+fn slices(len: u32, start: u32, end: u32) -> [(i32, u32); 3] {
+    let (start, end) = if start + end > len {
+        let start = (start as u64 * len as u64 / (start + end) as u64) as u32;
+        (start, len - start)
+    } else {
+        (start, end)
+    };
+    let middle = len - start - end;
+
+    [
+        (0, start),
+        (start as i32, middle),
+        ((start + middle) as i32, end),
+    ]
+}
+
+/// Copies `src` into `dest` by repeating it along the tiled axes, cropping the last tile.
+fn copy_tiled(
+    canvas: &mut Canvas<Window>,
+    raw: &TextureRaw,
+    src: SdlRect,
+    dest: SdlRect,
+    tile: bool,
+) -> Result<(), String> {
+    if !tile || src.width() == 0 || src.height() == 0 {
+        return canvas.copy(raw, src, dest);
+    }
+
+    let mut y = 0;
+    while y < dest.height() {
+        let h = src.height().min(dest.height() - y);
+
+        let mut x = 0;
+        while x < dest.width() {
+            let w = src.width().min(dest.width() - x);
+
+            canvas.copy(
+                raw,
+                SdlRect::new(src.x(), src.y(), w, h),
+                SdlRect::new(dest.x() + x as i32, dest.y() + y as i32, w, h),
+            )?;
+
+            x += w;
+        }
+
+        y += h;
+    }
+
+    Ok(())
+}
+
+impl Drawable for NineSlice {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        let region = match ctx.resolve_texture(&self.texture)? {
+            Some(region) => region,
+            None => return Ok(()),
+        };
+        let mut raw = region.raw.borrow_mut();
+
+        let (width, height) = region.size();
+        let insets = self.insets;
+        if insets.left + insets.right > width || insets.top + insets.bottom > height {
+            return Err(format!(
+                "The insets of the nine slice are larger than the texture {:?}",
+                self.texture
+            )
+            .into());
+        }
+
+        let src_cols = slices(width, insets.left, insets.right);
+        let src_rows = slices(height, insets.top, insets.bottom);
+        let dest_cols = slices(self.dest.width, insets.left, insets.right);
+        let dest_rows = slices(self.dest.height, insets.top, insets.bottom);
+
+        let canvas = ctx.canvas();
+        let mut canvas = canvas.borrow_mut();
+
+        with_texture_blend_mode(&mut raw, self.blend_mode, |raw| {
+            Ok(with_tint(raw, self.color_tint, self.alpha_tint, |raw| {
+                for row in 0..3 {
+                    for col in 0..3 {
+                        let (src_x, src_w) = src_cols[col];
+                        let (src_y, src_h) = src_rows[row];
+                        let (dest_x, dest_w) = dest_cols[col];
+                        let (dest_y, dest_h) = dest_rows[row];
+
+                        if src_w == 0 || src_h == 0 || dest_w == 0 || dest_h == 0 {
+                            continue;
+                        }
+
+                        let is_corner = row != 1 && col != 1;
+                        let is_center = row == 1 && col == 1;
+                        let tile = !is_corner
+                            && if is_center {
+                                self.center_mode == SliceMode::Tile
+                            } else {
+                                self.edge_mode == SliceMode::Tile
+                            };
+
+                        let src = region
+                            .src(Some(Rect::new((src_x, src_y), src_w, src_h)))
+                            .unwrap();
+
+                        copy_tiled(
+                            &mut canvas,
+                            raw,
+                            src.into(),
+                            SdlRect::new(
+                                self.dest.x + dest_x,
+                                self.dest.y + dest_y,
+                                dest_w,
+                                dest_h,
+                            ),
+                            tile,
+                        )?;
+                    }
+                }
+
+                Ok::<(), String>(())
+            })?)
+        })
+    }
+}
@@ -0,0 +1,301 @@
+// NOTE: Only depends on `std`, since the `mgi-pack` tool includes this file directly
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// Written at the start of every pack, followed by the version of the format
+const MAGIC: &[u8; 8] = b"MGIPACK1";
+
+/// The size of an entry of the index with an empty path
+const MIN_ENTRY_LEN: u64 = 4 + 8 + 8;
+
+/// A file inside a pack
+struct Entry {
+    offset: u64,
+    len: u64,
+}
+
+/// An archive of files, made with the `mgi-pack` tool.
+///
+/// The pack starts with `MAGIC` and the number of files (as a little endian `u32`), followed by
+/// the index: for every file, the length of its path (`u32`), its path (UTF-8, with `/` as the
+/// separator), and the offset (from the start of the pack) and length of its data (`u64`s). The
+/// data of the files comes after the index.
+pub(crate) struct Pack {
+    path: PathBuf,
+    entries: HashMap<String, Entry>,
+}
+
+impl Pack {
+    /// Only reads the index: the files are read when they are used.
+    ///
+    /// The index is checked against the size of the pack, so a corrupted pack is an error rather
+    /// than a huge allocation or a read past its end.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let pack_len = file.metadata()?.len();
+        let mut file = io::BufReader::new(file);
+
+        let mut magic = [0; 8];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data(
+                "not a pack, or made with another version of mgi-pack",
+            ));
+        }
+
+        let count = read_u32(&mut file)? as u64;
+        let mut position = MAGIC.len() as u64 + 4;
+        if count > pack_len.saturating_sub(position) / MIN_ENTRY_LEN {
+            return Err(invalid_data("the index is longer than the pack"));
+        }
+
+        let mut entries = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let name_len = read_u32(&mut file)? as u64;
+            position += MIN_ENTRY_LEN + name_len;
+            if position > pack_len {
+                return Err(invalid_data("the index is longer than the pack"));
+            }
+
+            let mut name = vec![0; name_len as usize];
+            file.read_exact(&mut name)?;
+            let name = String::from_utf8(name).map_err(|e| invalid_data(&e.to_string()))?;
+
+            let offset = read_u64(&mut file)?;
+            let len = read_u64(&mut file)?;
+            match offset.checked_add(len) {
+                Some(end) if end <= pack_len => {}
+                _ => return Err(invalid_data(&format!("`{}` is outside of the pack", name))),
+            }
+            entries.insert(name, Entry { offset, len });
+        }
+
+        Ok(Self {
+            path: path.into(),
+            entries,
+        })
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Returns `None` if the pack doesn't contain the file
+    pub(crate) fn read(&self, name: &str) -> Option<io::Result<Vec<u8>>> {
+        let entry = self.entries.get(name)?;
+
+        // Opened for every read, so packs can be read from several threads at once
+        let read = || {
+            let mut file = File::open(&self.path)?;
+            file.seek(SeekFrom::Start(entry.offset))?;
+
+            // Checked against the size of the pack when it was opened, but it may have changed
+            let len = usize::try_from(entry.len)
+                .map_err(|_| invalid_data(&format!("`{}` is too large", name)))?;
+            let mut data = Vec::with_capacity(len.min(1 << 20));
+            (&mut file).take(entry.len).read_to_end(&mut data)?;
+            if data.len() != len {
+                return Err(invalid_data(&format!("`{}` is outside of the pack", name)));
+            }
+
+            Ok(data)
+        };
+
+        Some(read())
+    }
+}
+
+/// Packs every file inside `directory` (recursively) into `output`, returning the number of files
+pub(crate) fn write(directory: &Path, output: &Path) -> io::Result<usize> {
+    let mut files = Vec::new();
+    list_files(directory, &mut files)?;
+    files.sort();
+
+    // The output may be inside the directory
+    let output_path = fs::canonicalize(output.parent().unwrap_or_else(|| Path::new(".")))
+        .map(|parent| parent.join(output.file_name().unwrap_or_default()))
+        .unwrap_or_default();
+    files.retain(|file| fs::canonicalize(file).map_or(true, |file| file != output_path));
+
+    let names: Vec<_> = files
+        .iter()
+        .map(|file| normalize(&file.strip_prefix(directory).unwrap().to_string_lossy()))
+        .collect();
+
+    let index_len: u64 = names.iter().map(|name| 4 + name.len() as u64 + 16).sum();
+
+    let mut out = BufWriter::new(File::create(output)?);
+    out.write_all(MAGIC)?;
+    out.write_all(&(files.len() as u32).to_le_bytes())?;
+
+    let mut offset = MAGIC.len() as u64 + 4 + index_len;
+    for (file, name) in files.iter().zip(&names) {
+        let len = fs::metadata(file)?.len();
+
+        out.write_all(&(name.len() as u32).to_le_bytes())?;
+        out.write_all(name.as_bytes())?;
+        out.write_all(&offset.to_le_bytes())?;
+        out.write_all(&len.to_le_bytes())?;
+
+        offset += len;
+    }
+
+    for file in &files {
+        io::copy(&mut File::open(file)?, &mut out)?;
+    }
+    out.flush()?;
+
+    Ok(files.len())
+}
+
+/// Turns the path into the form used inside packs: relative, with `/` as the separator and without
+/// `.` or `..`
+pub(crate) fn normalize(path: &str) -> String {
+    let mut components = Vec::new();
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+
+    components.join("/")
+}
+
+fn list_files(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// An empty directory for the test, removed when dropped
+    pub(crate) struct TempDir(pub(crate) PathBuf);
+
+    impl TempDir {
+        pub(crate) fn new(test: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("mgi-pack-{}-{}", std::process::id(), test));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn normalizes_paths() {
+        assert_eq!(normalize("a/b.png"), "a/b.png");
+        assert_eq!(normalize("./a//b.png"), "a/b.png");
+        assert_eq!(normalize("a\\b\\c.png"), "a/b/c.png");
+        assert_eq!(normalize("a/../b/./c.png"), "b/c.png");
+        assert_eq!(normalize("/a/b.png"), "a/b.png");
+        assert_eq!(normalize("../a.png"), "a.png");
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = TempDir::new("round-trip");
+        fs::create_dir_all(dir.0.join("assets/tiles")).unwrap();
+        fs::write(dir.0.join("assets/bg.png"), b"background").unwrap();
+        fs::write(dir.0.join("assets/tiles/ground.png"), b"ground").unwrap();
+        fs::write(dir.0.join("assets/empty"), b"").unwrap();
+
+        // Written inside the directory, so it must not pack itself
+        let output = dir.0.join("assets/game.pack");
+        assert_eq!(write(&dir.0.join("assets"), &output).unwrap(), 3);
+
+        let pack = Pack::open(&output).unwrap();
+        assert_eq!(pack.entries.len(), 3);
+        assert_eq!(pack.read("bg.png").unwrap().unwrap(), b"background");
+        assert_eq!(pack.read("tiles/ground.png").unwrap().unwrap(), b"ground");
+        assert_eq!(pack.read("empty").unwrap().unwrap(), b"");
+        assert!(pack.contains("tiles/ground.png"));
+        assert!(!pack.contains("game.pack"));
+        assert!(pack.read("missing.png").is_none());
+    }
+
+    #[test]
+    fn rejects_corrupted_packs() {
+        let dir = TempDir::new("corrupted");
+        let path = dir.0.join("bad.pack");
+        let open = |bytes: &[u8]| {
+            fs::write(&path, bytes).unwrap();
+            Pack::open(&path).map(|_| ())
+        };
+
+        let header = |count: u32| [&MAGIC[..], &count.to_le_bytes()].concat();
+        let entry = |name: &str, offset: u64, len: u64| {
+            [
+                &(name.len() as u32).to_le_bytes()[..],
+                name.as_bytes(),
+                &offset.to_le_bytes(),
+                &len.to_le_bytes(),
+            ]
+            .concat()
+        };
+
+        assert!(open(b"NOTAPACK\0\0\0\0").is_err());
+        assert!(open(&header(0)).is_ok());
+
+        // More entries than the pack can hold
+        assert!(open(&header(u32::MAX)).is_err());
+
+        // A path longer than the pack
+        let mut bytes = header(1);
+        bytes.extend(entry("a", 0, 0));
+        bytes[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(open(&bytes).is_err());
+
+        // Data past the end of the pack, or overflowing
+        for (offset, len) in [(0, 1000), (u64::MAX, 2), (30, u64::MAX)] {
+            let bytes = [header(1), entry("a", offset, len)].concat();
+            assert!(open(&bytes).is_err());
+        }
+
+        let bytes = [header(1), entry("a", 33, 2), b"hi".to_vec()].concat();
+        fs::write(&path, &bytes).unwrap();
+        let pack = Pack::open(&path).unwrap();
+        assert_eq!(pack.read("a").unwrap().unwrap(), b"hi");
+    }
+}
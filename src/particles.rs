@@ -0,0 +1,403 @@
+use std::{
+    ops::RangeInclusive,
+    sync::atomic::{AtomicU32, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sdl2::{pixels::Color, rect::Rect as SdlRect};
+
+use crate::{
+    blend::{with_canvas_blend_mode, with_texture_blend_mode},
+    drawable::Drawable,
+    prelude::*,
+    texture_manager::TextureRegion,
+};
+
+/// A value that changes over the life of a particle.
+///
+/// The keys are `(t, value)` pairs, where `t` is the fraction of the particle's life that has
+/// passed (`0.0..=1.0`); values between keys are linearly interpolated.
+#[derive(Clone)]
+pub struct Curve<T: Lerp + Clone> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: Lerp + Clone> Curve<T> {
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0., value)],
+        }
+    }
+
+    pub fn linear(start: T, end: T) -> Self {
+        Self {
+            keys: vec![(0., start), (1., end)],
+        }
+    }
+
+    /// Adds a key to the curve (keys can be added in any order)
+    pub fn key(mut self, t: f32, value: T) -> Self {
+        let idx = self.keys.partition_point(|(key_t, _)| *key_t <= t);
+        self.keys.insert(idx, (t, value));
+        self
+    }
+
+    pub fn sample(&self, t: f32) -> T {
+        let idx = self.keys.partition_point(|(key_t, _)| *key_t <= t);
+
+        if idx == 0 {
+            return self.keys[0].1.clone();
+        } else if idx == self.keys.len() {
+            return self.keys[idx - 1].1.clone();
+        }
+
+        let (t0, v0) = &self.keys[idx - 1];
+        let (t1, v1) = &self.keys[idx];
+        v0.lerp(v1, (t - t0) / (t1 - t0))
+    }
+}
+
+/// A small xorshift generator, so particles don't need an external RNG
+#[derive(Clone)]
+struct Rng(u32);
+
+/// Counts the emitters created, so each one gets a different seed
+static EMITTERS: AtomicU32 = AtomicU32::new(0);
+
+impl Rng {
+    /// Xorshift gets stuck on 0, so the seed is scrambled into a non-zero state
+    fn new(seed: u32) -> Self {
+        let state = seed.wrapping_add(0x9E37_79B9).wrapping_mul(0x85EB_CA6B);
+        Self((state ^ (state >> 16)).max(1))
+    }
+
+    /// A different seed for every emitter, that also changes every time the game runs
+    fn unique() -> Self {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.subsec_nanos());
+        let count = EMITTERS.fetch_add(1, Ordering::Relaxed);
+
+        Self::new(time ^ count.wrapping_mul(0x27D4_EB2F))
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+
+    fn range(&mut self, range: &RangeInclusive<f32>) -> f32 {
+        range.start() + (range.end() - range.start()) * self.next_f32()
+    }
+}
+
+/// The live particles, stored as a struct of arrays.
+///
+/// Dead particles are swap-removed, so the live particles are always packed at the front, and
+/// the storage is reused once it has grown to `max_particles`.
+#[derive(Clone, Default)]
+struct Particles {
+    positions: Vec<Vec2f>,
+    velocities: Vec<Vec2f>,
+    accelerations: Vec<Vec2f>,
+    ages: Vec<f32>,
+    lifetimes: Vec<f32>,
+}
+
+impl Particles {
+    fn len(&self) -> usize {
+        self.ages.len()
+    }
+
+    fn swap_remove(&mut self, idx: usize) {
+        self.positions.swap_remove(idx);
+        self.velocities.swap_remove(idx);
+        self.accelerations.swap_remove(idx);
+        self.ages.swap_remove(idx);
+        self.lifetimes.swap_remove(idx);
+    }
+}
+
+/// Spawns, simulates, and draws (through `Context::draw_particles`) a set of particles.
+#[derive(Clone)]
+pub struct ParticleEmitter {
+    position: Vec2f,
+    spawn_area: (f32, f32),
+    emitting: bool,
+    spawn_rate: f32,
+    spawn_accumulator: f32,
+    max_particles: usize,
+
+    lifetime: RangeInclusive<f32>,
+    velocity: (RangeInclusive<f32>, RangeInclusive<f32>),
+    acceleration: (RangeInclusive<f32>, RangeInclusive<f32>),
+    gravity: Vec2f,
+
+    color_over_life: Curve<Color>,
+    alpha_over_life: Curve<f32>,
+    size_over_life: Curve<f32>,
+
+    texture: Option<AssetKey<Texture>>,
+    blend_mode: Option<BlendMode>,
+
+    particles: Particles,
+    rng: Rng,
+}
+
+impl ParticleEmitter {
+    pub fn new(position: impl Into<Vec2f>) -> Self {
+        Self {
+            position: position.into(),
+            spawn_area: (0., 0.),
+            emitting: true,
+            spawn_rate: 10.,
+            spawn_accumulator: 0.,
+            max_particles: 1000,
+            lifetime: 1.0..=1.0,
+            velocity: (0.0..=0.0, 0.0..=0.0),
+            acceleration: (0.0..=0.0, 0.0..=0.0),
+            gravity: Vec2f::ZERO,
+            color_over_life: Curve::constant(Color::WHITE),
+            alpha_over_life: Curve::constant(1.),
+            size_over_life: Curve::constant(4.),
+            texture: None,
+            blend_mode: None,
+            particles: Particles::default(),
+            rng: Rng::unique(),
+        }
+    }
+
+    /// The size of the area, centered on the emitter's position, that particles spawn in
+    pub fn spawn_area(mut self, width: f32, height: f32) -> Self {
+        self.spawn_area = (width, height);
+        self
+    }
+
+    /// Particles spawned per second
+    pub fn spawn_rate(mut self, rate: f32) -> Self {
+        self.spawn_rate = rate;
+        self
+    }
+
+    pub fn max_particles(mut self, max: usize) -> Self {
+        self.max_particles = max;
+        self
+    }
+
+    /// Lifetime of each particle in seconds
+    pub fn lifetime(mut self, lifetime: RangeInclusive<f32>) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Initial velocity of each particle, in pixels per second
+    pub fn velocity(mut self, x: RangeInclusive<f32>, y: RangeInclusive<f32>) -> Self {
+        self.velocity = (x, y);
+        self
+    }
+
+    pub fn acceleration(mut self, x: RangeInclusive<f32>, y: RangeInclusive<f32>) -> Self {
+        self.acceleration = (x, y);
+        self
+    }
+
+    /// Acceleration applied to every particle, in pixels per second squared
+    pub fn gravity(mut self, gravity: impl Into<Vec2f>) -> Self {
+        self.gravity = gravity.into();
+        self
+    }
+
+    pub fn color_over_life(mut self, curve: Curve<Color>) -> Self {
+        self.color_over_life = curve;
+        self
+    }
+
+    /// Alpha in the range `0.0..=1.0`
+    pub fn alpha_over_life(mut self, curve: Curve<f32>) -> Self {
+        self.alpha_over_life = curve;
+        self
+    }
+
+    /// Size of each particle in pixels
+    pub fn size_over_life(mut self, curve: Curve<f32>) -> Self {
+        self.size_over_life = curve;
+        self
+    }
+
+    /// Draws each particle with the texture (particles are drawn as squares if this isn't set)
+    pub fn texture(mut self, texture: impl Into<AssetKey<Texture>>) -> Self {
+        self.texture = Some(texture.into());
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    /// Makes the emitter spawn the same particles every time it is run with the same seed (e.g.
+    /// for replays), instead of a different pattern for every emitter
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    pub fn set_position(&mut self, position: impl Into<Vec2f>) {
+        self.position = position.into();
+    }
+
+    /// Starts or stops continuous spawning (bursts can still be emitted)
+    pub fn set_emitting(&mut self, emitting: bool) {
+        self.emitting = emitting;
+    }
+
+    pub(crate) fn texture_key(&self) -> Option<&AssetKey<Texture>> {
+        self.texture.as_ref()
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Immediately spawns `count` particles
+    pub fn burst(&mut self, count: usize) {
+        for _ in 0..count {
+            self.spawn();
+        }
+    }
+
+    /// Spawns new particles and simulates the existing ones
+    pub fn update(&mut self, dt: f32) {
+        if self.emitting {
+            self.spawn_accumulator += self.spawn_rate * dt;
+            while self.spawn_accumulator >= 1. {
+                self.spawn();
+                self.spawn_accumulator -= 1.;
+            }
+        }
+
+        let particles = &mut self.particles;
+        let mut idx = 0;
+        while idx < particles.len() {
+            particles.ages[idx] += dt;
+            if particles.ages[idx] >= particles.lifetimes[idx] {
+                particles.swap_remove(idx);
+                continue;
+            }
+
+            particles.velocities[idx] += (particles.accelerations[idx] + self.gravity) * dt;
+            particles.positions[idx] += particles.velocities[idx] * dt;
+
+            idx += 1;
+        }
+    }
+
+    fn spawn(&mut self) {
+        if self.particles.len() >= self.max_particles {
+            return;
+        }
+
+        let rng = &mut self.rng;
+        let offset = Vec2f::new(
+            (rng.next_f32() - 0.5) * self.spawn_area.0,
+            (rng.next_f32() - 0.5) * self.spawn_area.1,
+        );
+
+        let particles = &mut self.particles;
+        particles.positions.push(self.position + offset);
+        particles.velocities.push(Vec2f::new(
+            rng.range(&self.velocity.0),
+            rng.range(&self.velocity.1),
+        ));
+        particles.accelerations.push(Vec2f::new(
+            rng.range(&self.acceleration.0),
+            rng.range(&self.acceleration.1),
+        ));
+        particles.ages.push(0.);
+        particles.lifetimes.push(rng.range(&self.lifetime));
+    }
+
+    /// The destination and color of every live particle
+    fn quads(&self) -> Vec<(SdlRect, Color)> {
+        let particles = &self.particles;
+
+        (0..particles.len())
+            .map(|idx| {
+                let t = particles.ages[idx] / particles.lifetimes[idx];
+                let size = self.size_over_life.sample(t).max(0.);
+                let mut color = self.color_over_life.sample(t);
+                color.a = (color.a as f32 * self.alpha_over_life.sample(t).clamp(0., 1.)) as u8;
+
+                let top_left = Vec2::from(particles.positions[idx] - Vec2f::new(size, size) / 2.);
+                let dest = SdlRect::new(top_left.x, top_left.y, size as u32, size as u32);
+
+                (dest, color)
+            })
+            .collect()
+    }
+}
+
+/// A snapshot of an emitter's particles, submitted to a layer by `Context::draw_particles`
+pub(crate) struct ParticleBatch {
+    pub(crate) texture: Option<TextureRegion>,
+    pub(crate) quads: Vec<(SdlRect, Color)>,
+    pub(crate) blend_mode: Option<BlendMode>,
+}
+
+impl ParticleBatch {
+    pub(crate) fn new(emitter: &ParticleEmitter, texture: Option<TextureRegion>) -> Self {
+        Self {
+            texture,
+            quads: emitter.quads(),
+            blend_mode: emitter.blend_mode,
+        }
+    }
+}
+
+impl Drawable for ParticleBatch {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        let canvas = ctx.canvas();
+        let mut canvas = canvas.borrow_mut();
+
+        if let Some(texture) = &self.texture {
+            let src = texture.src(None).map(SdlRect::from);
+
+            with_texture_blend_mode(&mut texture.raw.borrow_mut(), self.blend_mode, |raw| {
+                let (r, g, b) = raw.color_mod();
+                let a = raw.alpha_mod();
+
+                let mut res = Ok(());
+                for (dest, color) in &self.quads {
+                    raw.set_color_mod(color.r, color.g, color.b);
+                    raw.set_alpha_mod(color.a);
+
+                    res = canvas.copy(raw, src, *dest);
+                    if res.is_err() {
+                        break;
+                    }
+                }
+
+                raw.set_color_mod(r, g, b);
+                raw.set_alpha_mod(a);
+
+                Ok(res?)
+            })
+        } else {
+            // Untextured particles are alpha blended by default, so they can fade out
+            let blend_mode = self.blend_mode.or(Some(BlendMode::Alpha));
+
+            with_canvas_blend_mode(&mut canvas, blend_mode, |canvas| {
+                for (dest, color) in &self.quads {
+                    canvas.set_draw_color(*color);
+                    canvas.fill_rect(*dest)?;
+                }
+
+                canvas.set_draw_color(ctx.clear_color);
+
+                Ok(())
+            })
+        }
+    }
+}
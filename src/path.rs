@@ -0,0 +1,669 @@
+use std::f32::consts::PI;
+
+use sdl2::pixels::Color;
+
+use crate::{
+    drawable::Drawable,
+    mesh::{Mesh, Vertex},
+    prelude::*,
+    scene::SceneDrawable,
+    transform::Matrix2D,
+};
+
+/// The maximum distance, in pixels, between a curve and the line segments it is flattened into
+const TOLERANCE: f32 = 0.25;
+
+/// Decides which parts of a path are inside of it, for overlapping or self intersecting paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Points that the path winds around a non-zero number of times are inside
+    NonZero,
+
+    /// Points that the path winds around an odd number of times are inside
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// How the corners between the segments of a stroke are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// How the ends of an unclosed stroke are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end points
+    Butt,
+    Round,
+
+    /// The stroke is extended past the end points by half its width
+    Square,
+}
+
+/// The outline drawn along a `Path`
+#[derive(Debug, Clone, Copy)]
+pub struct Stroke {
+    pub width: f32,
+    pub color: Color,
+    pub join: LineJoin,
+    pub cap: LineCap,
+
+    /// Miter joins longer than `miter_limit * width / 2` are drawn as bevel joins instead
+    pub miter_limit: f32,
+}
+
+impl Stroke {
+    pub fn new(width: f32, color: Color) -> Self {
+        Self {
+            width,
+            color,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.,
+        }
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SubPath {
+    points: Vec<Vec2f>,
+    closed: bool,
+}
+
+/// A vector shape made of lines and curves, which can be filled and/or stroked.
+///
+/// Curves are flattened into line segments as they are added, and the path is tessellated into
+/// a `Mesh` when drawn (use `to_mesh` to only tessellate it once).
+///
+/// NOTE: Overlapping parts of a stroke (e.g. at its joins) are blended more than once, so
+/// translucent strokes will have darker spots there
+#[derive(Debug, Clone, Default)]
+pub struct Path {
+    subpaths: Vec<SubPath>,
+    fill: Option<(Color, FillRule)>,
+    stroke: Option<Stroke>,
+    blend_mode: Option<BlendMode>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new subpath at `point`
+    pub fn move_to(mut self, point: impl Into<Vec2f>) -> Self {
+        self.subpaths.push(SubPath {
+            points: vec![point.into()],
+            closed: false,
+        });
+        self
+    }
+
+    pub fn line_to(mut self, point: impl Into<Vec2f>) -> Self {
+        let point = point.into();
+        self.current_subpath(point).points.push(point);
+        self
+    }
+
+    /// Adds a quadratic bezier curve from the current point to `to`
+    pub fn quad_to(mut self, control: impl Into<Vec2f>, to: impl Into<Vec2f>) -> Self {
+        let (control, to) = (control.into(), to.into());
+        let subpath = self.current_subpath(control);
+        let from = *subpath.points.last().unwrap();
+
+        let segments = curve_segments(&[from, control, to]);
+        subpath.points.extend((1..=segments).map(|i| {
+            let t = i as f32 / segments as f32;
+            from * (1. - t).powi(2) + control * (2. * (1. - t) * t) + to * t.powi(2)
+        }));
+        self
+    }
+
+    /// Adds a cubic bezier curve from the current point to `to`
+    pub fn cubic_to(
+        mut self,
+        control1: impl Into<Vec2f>,
+        control2: impl Into<Vec2f>,
+        to: impl Into<Vec2f>,
+    ) -> Self {
+        let (control1, control2, to) = (control1.into(), control2.into(), to.into());
+        let subpath = self.current_subpath(control1);
+        let from = *subpath.points.last().unwrap();
+
+        let segments = curve_segments(&[from, control1, control2, to]);
+        subpath.points.extend((1..=segments).map(|i| {
+            let t = i as f32 / segments as f32;
+            from * (1. - t).powi(3)
+                + control1 * (3. * (1. - t).powi(2) * t)
+                + control2 * (3. * (1. - t) * t.powi(2))
+                + to * t.powi(3)
+        }));
+        self
+    }
+
+    /// Adds a circular arc from the angle `start` to `end` (clockwise on the screen if `end` is
+    /// greater than `start`).
+    ///
+    /// If the path has a current point, a line is added from it to the start of the arc.
+    pub fn arc(
+        mut self,
+        center: impl Into<Vec2f>,
+        radius: f32,
+        start: Rotation,
+        end: Rotation,
+    ) -> Self {
+        let center = center.into();
+        let (start, end) = (start.to_radians(), end.to_radians());
+        let point_at =
+            |angle: f32| center + Vec2f::new(radius, 0.).rotate(Rotation::Radians(angle));
+
+        let subpath = self.current_subpath(point_at(start));
+        if subpath.points.last() != Some(&point_at(start)) {
+            subpath.points.push(point_at(start));
+        }
+
+        let segments = arc_segments(radius, end - start);
+        subpath.points.extend(
+            (1..=segments).map(|i| point_at(start + (end - start) * i as f32 / segments as f32)),
+        );
+        self
+    }
+
+    /// Closes the current subpath with a line back to its start
+    pub fn close(mut self) -> Self {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+        self
+    }
+
+    pub fn fill(mut self, color: Color, rule: FillRule) -> Self {
+        self.fill = Some((color, rule));
+        self
+    }
+
+    pub fn stroke(mut self, stroke: Stroke) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    /// Tessellates the fill and stroke of the path into triangles
+    pub fn to_mesh(&self) -> Mesh {
+        let mut vertices = Vec::new();
+
+        if let Some((color, rule)) = self.fill {
+            tessellate_fill(&self.subpaths, rule, color, &mut vertices);
+        }
+
+        if let Some(stroke) = &self.stroke {
+            for subpath in &self.subpaths {
+                tessellate_stroke(subpath, stroke, &mut vertices);
+            }
+        }
+
+        let mesh = Mesh::new(vertices);
+        match self.blend_mode {
+            Some(blend_mode) => mesh.blend_mode(blend_mode),
+            None => mesh,
+        }
+    }
+
+    /// The subpath that is being added to, starting a new one at `point` if there's none (or the
+    /// last one was closed, in which case the new one starts where the closed one did)
+    fn current_subpath(&mut self, point: Vec2f) -> &mut SubPath {
+        match self.subpaths.last() {
+            None => self.subpaths.push(SubPath {
+                points: vec![point],
+                closed: false,
+            }),
+            Some(last) if last.closed => {
+                let start = last.points[0];
+                self.subpaths.push(SubPath {
+                    points: vec![start],
+                    closed: false,
+                });
+            }
+            Some(_) => {}
+        }
+
+        self.subpaths.last_mut().unwrap()
+    }
+}
+
+impl Drawable for Path {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        self.to_mesh().draw(ctx)
+    }
+}
+
+impl SceneDrawable for Path {
+    fn draw_transformed(&mut self, ctx: &Context, transform: &Matrix2D) -> MgiResult<()> {
+        self.to_mesh().draw_transformed(ctx, transform)
+    }
+}
+
+/// The number of line segments to flatten a bezier curve into, so that it's within `TOLERANCE`
+/// of the curve (based on the curve's second derivative)
+fn curve_segments(points: &[Vec2f]) -> usize {
+    let degree = (points.len() - 1) as f32;
+    let second_derivative = points
+        .windows(3)
+        .map(|p| (p[0] - p[1] * 2. + p[2]).length())
+        .fold(0., f32::max)
+        * degree
+        * (degree - 1.);
+
+    (((second_derivative / (8. * TOLERANCE)).sqrt().ceil()) as usize).clamp(1, 128)
+}
+
+/// The number of line segments to flatten an arc into
+fn arc_segments(radius: f32, sweep: f32) -> usize {
+    let step = if radius > TOLERANCE {
+        2. * (1. - TOLERANCE / radius).acos()
+    } else {
+        PI
+    };
+
+    ((sweep.abs() / step).ceil() as usize).clamp(1, 256)
+}
+
+fn push_triangle(vertices: &mut Vec<Vertex>, points: [Vec2f; 3], color: Color) {
+    vertices.extend(points.map(|point| Vertex::new(point, color, Vec2f::ZERO)));
+}
+
+/// An edge of a filled path, going downwards
+struct Edge {
+    top: Vec2f,
+    bottom: Vec2f,
+
+    /// `1` if the path goes down along this edge, `-1` if it goes up
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        self.top.x + (y - self.top.y) * (self.bottom.x - self.top.x) / (self.bottom.y - self.top.y)
+    }
+}
+
+/// Returns the y coordinate where the edges cross, if they do
+fn intersection_y(a: &Edge, b: &Edge) -> Option<f32> {
+    let (da, db) = (a.bottom - a.top, b.bottom - b.top);
+    let denominator = da.cross(db);
+    if denominator == 0. {
+        return None;
+    }
+
+    let t = (b.top - a.top).cross(db) / denominator;
+    let u = (b.top - a.top).cross(da) / denominator;
+
+    (t > 0. && t < 1. && u > 0. && u < 1.).then_some(a.top.y + t * da.y)
+}
+
+/// Splits the filled area into horizontal bands that no edges cross within (every vertex and
+/// intersection starts a new band), and fills the trapezoids between the edges in each band that
+/// are inside of the path. Every subpath is implicitly closed.
+fn tessellate_fill(subpaths: &[SubPath], rule: FillRule, color: Color, vertices: &mut Vec<Vertex>) {
+    let edges: Vec<Edge> = subpaths
+        .iter()
+        .flat_map(|subpath| {
+            let points = &subpath.points;
+            (0..points.len()).map(move |i| (points[i], points[(i + 1) % points.len()]))
+        })
+        .filter(|(from, to)| from.y != to.y)
+        .map(|(from, to)| {
+            if from.y < to.y {
+                Edge {
+                    top: from,
+                    bottom: to,
+                    winding: 1,
+                }
+            } else {
+                Edge {
+                    top: to,
+                    bottom: from,
+                    winding: -1,
+                }
+            }
+        })
+        .collect();
+
+    let mut ys: Vec<f32> = edges
+        .iter()
+        .flat_map(|edge| [edge.top.y, edge.bottom.y])
+        .collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            let overlaps = a.top.y < b.bottom.y && b.top.y < a.bottom.y;
+            if let Some(y) = overlaps.then(|| intersection_y(a, b)).flatten() {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(f32::total_cmp);
+    ys.dedup_by(|a, b| (*a - *b).abs() < 1e-4);
+
+    for band in ys.windows(2) {
+        let (top, bottom) = (band[0], band[1]);
+        let middle = (top + bottom) / 2.;
+
+        let mut active: Vec<&Edge> = edges
+            .iter()
+            .filter(|edge| edge.top.y < middle && edge.bottom.y > middle)
+            .collect();
+        active.sort_by(|a, b| a.x_at(middle).total_cmp(&b.x_at(middle)));
+
+        let mut winding = 0;
+        for pair in active.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            winding += left.winding;
+
+            if rule.is_inside(winding) {
+                let top_left = Vec2f::new(left.x_at(top), top);
+                let top_right = Vec2f::new(right.x_at(top), top);
+                let bottom_left = Vec2f::new(left.x_at(bottom), bottom);
+                let bottom_right = Vec2f::new(right.x_at(bottom), bottom);
+
+                push_triangle(vertices, [top_left, top_right, bottom_right], color);
+                push_triangle(vertices, [top_left, bottom_right, bottom_left], color);
+            }
+        }
+    }
+}
+
+/// Adds a fan of triangles around `center`, from `center + from` rotated by `sweep` radians
+fn push_fan(vertices: &mut Vec<Vertex>, center: Vec2f, from: Vec2f, sweep: f32, color: Color) {
+    let segments = arc_segments(from.length(), sweep);
+
+    let mut previous = center + from;
+    for i in 1..=segments {
+        let point = center + from.rotate(Rotation::Radians(sweep * i as f32 / segments as f32));
+        push_triangle(vertices, [center, previous, point], color);
+        previous = point;
+    }
+}
+
+fn tessellate_stroke(subpath: &SubPath, stroke: &Stroke, vertices: &mut Vec<Vertex>) {
+    let half_width = stroke.width / 2.;
+    let color = stroke.color;
+
+    let mut points = subpath.points.clone();
+    points.dedup_by(|a, b| a.distance(*b) < 1e-4);
+    if subpath.closed && points.len() > 1 && points[0].distance(points[points.len() - 1]) < 1e-4 {
+        points.pop();
+    }
+    if points.len() < 2 || half_width <= 0. {
+        return;
+    }
+
+    let closed = subpath.closed && points.len() > 2;
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    let direction = |i: usize| (points[(i + 1) % points.len()] - points[i]).normalize();
+    let normal = |direction: Vec2f| Vec2f::new(-direction.y, direction.x) * half_width;
+
+    for i in 0..segment_count {
+        let dir = direction(i);
+        let (mut from, mut to) = (points[i], points[(i + 1) % points.len()]);
+
+        if !closed && stroke.cap == LineCap::Square {
+            if i == 0 {
+                from -= dir * half_width;
+            }
+            if i == segment_count - 1 {
+                to += dir * half_width;
+            }
+        }
+
+        let n = normal(dir);
+        push_triangle(vertices, [from + n, to + n, to - n], color);
+        push_triangle(vertices, [from + n, to - n, from - n], color);
+    }
+
+    let joins = if closed {
+        0..points.len()
+    } else {
+        1..points.len() - 1
+    };
+    for i in joins {
+        let point = points[i];
+        let (dir_in, dir_out) = (
+            direction((i + points.len() - 1) % points.len()),
+            direction(i),
+        );
+
+        let turn = dir_in.cross(dir_out);
+        if turn.abs() < 1e-6 && dir_in.dot(dir_out) > 0. {
+            continue;
+        }
+
+        // The join is on the outside of the turn
+        let side = if turn > 0. { -1. } else { 1. };
+        let (outer_in, outer_out) = (normal(dir_in) * side, normal(dir_out) * side);
+
+        match stroke.join {
+            LineJoin::Round => {
+                let sweep = outer_in.cross(outer_out).atan2(outer_in.dot(outer_out));
+                push_fan(vertices, point, outer_in, sweep, color);
+            }
+            LineJoin::Miter | LineJoin::Bevel => {
+                let miter_dir = (outer_in + outer_out).normalize();
+                let cos = miter_dir.dot(outer_in) / half_width;
+                let miter_length = if cos > 0. { half_width / cos } else { f32::MAX };
+
+                if stroke.join == LineJoin::Miter && miter_length <= stroke.miter_limit * half_width
+                {
+                    let tip = point + miter_dir * miter_length;
+                    push_triangle(vertices, [point, point + outer_in, tip], color);
+                    push_triangle(vertices, [point, tip, point + outer_out], color);
+                } else {
+                    push_triangle(
+                        vertices,
+                        [point, point + outer_in, point + outer_out],
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    if !closed && stroke.cap == LineCap::Round {
+        let (first, last) = (points[0], points[points.len() - 1]);
+        push_fan(vertices, first, normal(direction(0)), PI, color);
+        push_fan(
+            vertices,
+            last,
+            -normal(direction(points.len() - 2)),
+            PI,
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(vertices: &[Vertex]) -> f32 {
+        vertices
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (t[0].position, t[1].position, t[2].position);
+                (b - a).cross(c - a).abs() / 2.
+            })
+            .sum()
+    }
+
+    fn fill(path: &Path, rule: FillRule) -> Vec<Vertex> {
+        let mut vertices = Vec::new();
+        tessellate_fill(&path.subpaths, rule, Color::WHITE, &mut vertices);
+        vertices
+    }
+
+    fn stroke(path: &Path, stroke: Stroke) -> Vec<Vertex> {
+        let mut vertices = Vec::new();
+        for subpath in &path.subpaths {
+            tessellate_stroke(subpath, &stroke, &mut vertices);
+        }
+        vertices
+    }
+
+    fn assert_close(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() <= tolerance, "{} is not {}", a, b);
+    }
+
+    #[test]
+    fn fills_a_square_with_two_triangles() {
+        let square = Path::new()
+            .move_to((0., 0.))
+            .line_to((10., 0.))
+            .line_to((10., 10.))
+            .line_to((0., 10.))
+            .close();
+
+        for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let vertices = fill(&square, rule);
+            assert_eq!(vertices.len(), 6);
+            assert_close(area(&vertices), 100., 1e-3);
+        }
+    }
+
+    #[test]
+    fn fill_rules_of_a_star() {
+        // A pentagram, whose center pentagon is wound around twice
+        let point = |i: usize| Vec2f::new(100., 0.).rotate(Rotation::Degrees(i as f32 * 144.));
+        let mut star = Path::new().move_to(point(0));
+        for i in 1..5 {
+            star = star.line_to(point(i));
+        }
+        let star = star.close();
+
+        let (outer, inner) = (100_f32, 100. * (3. - 5_f32.sqrt()) / 2.);
+        let pentagon = 2.5 * inner * inner * 72_f32.to_radians().sin();
+        let whole = 5. * outer * inner * 36_f32.to_radians().sin();
+
+        assert_close(area(&fill(&star, FillRule::NonZero)), whole, 0.5);
+        assert_close(area(&fill(&star, FillRule::EvenOdd)), whole - pentagon, 0.5);
+    }
+
+    #[test]
+    fn stroke_caps() {
+        let line = Path::new().move_to((0., 0.)).line_to((100., 0.));
+        let cap_area = |cap| area(&stroke(&line, Stroke::new(10., Color::WHITE).cap(cap)));
+
+        assert_close(cap_area(LineCap::Butt), 1000., 1e-2);
+        assert_close(cap_area(LineCap::Square), 1100., 1e-2);
+        // The round caps are polygons inside of the circle, within the tolerance of its edge
+        let round = cap_area(LineCap::Round);
+        let circle = PI * 25.;
+        assert!(round < 1000. + circle && round > 1000. + circle - TOLERANCE * 2. * PI * 5.);
+    }
+
+    #[test]
+    fn stroke_joins() {
+        let corner = Path::new()
+            .move_to((0., 0.))
+            .line_to((100., 0.))
+            .line_to((100., 100.));
+        let join_area = |stroke_: Stroke| area(&stroke(&corner, stroke_)) - 2000.;
+        let stroke_ = Stroke::new(10., Color::WHITE);
+
+        // The outer corner of the turn is a 5x5 square
+        assert_close(join_area(stroke_.join(LineJoin::Miter)), 25., 1e-2);
+        assert_close(join_area(stroke_.join(LineJoin::Bevel)), 12.5, 1e-2);
+        let round = join_area(stroke_.join(LineJoin::Round));
+        let quarter = PI * 25. / 4.;
+        assert!(round < quarter && round > quarter - TOLERANCE * PI * 5. / 2.);
+
+        // Past the miter limit, miter joins are beveled
+        let limited = stroke_.join(LineJoin::Miter).miter_limit(1.);
+        assert_close(join_area(limited), 12.5, 1e-2);
+    }
+
+    #[test]
+    fn closed_strokes_join_their_ends() {
+        let square = Path::new()
+            .move_to((0., 0.))
+            .line_to((100., 0.))
+            .line_to((100., 100.))
+            .line_to((0., 100.))
+            .close();
+        let vertices = stroke(&square, Stroke::new(10., Color::WHITE).cap(LineCap::Square));
+
+        // 4 segments and 4 miter joins, without caps
+        assert_eq!(vertices.len(), (4 * 2 + 4 * 2) * 3);
+        assert_close(area(&vertices), 4. * 1000. + 4. * 25., 1e-2);
+    }
+
+    #[test]
+    fn curves_are_flattened_within_the_tolerance() {
+        let (from, control, to) = (
+            Vec2f::new(0., 0.),
+            Vec2f::new(50., 200.),
+            Vec2f::new(100., 0.),
+        );
+        let path = Path::new().move_to(from).quad_to(control, to);
+        let points = &path.subpaths[0].points;
+        assert!(points.len() > 3);
+        assert_eq!(points[0], from);
+        assert_eq!(*points.last().unwrap(), to);
+
+        let curve: Vec<Vec2f> = (0..=10000)
+            .map(|i| {
+                let t = i as f32 / 10000.;
+                from * (1. - t).powi(2) + control * (2. * (1. - t) * t) + to * t.powi(2)
+            })
+            .collect();
+        for segment in points.windows(2) {
+            let middle = (segment[0] + segment[1]) / 2.;
+            let distance = curve
+                .iter()
+                .map(|point| point.distance(middle))
+                .fold(f32::MAX, f32::min);
+            assert!(distance <= TOLERANCE + 0.05, "{} from the curve", distance);
+        }
+
+        let arc = Path::new().arc((0., 0.), 50., Rotation::Degrees(0.), Rotation::Degrees(90.));
+        for point in &arc.subpaths[0].points {
+            assert_close(point.length(), 50., 1e-3);
+        }
+        for segment in arc.subpaths[0].points.windows(2) {
+            let middle = (segment[0] + segment[1]) / 2.;
+            assert!(50. - middle.length() <= TOLERANCE + 1e-3);
+        }
+    }
+}
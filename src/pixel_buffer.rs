@@ -0,0 +1,131 @@
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    rc::Rc,
+};
+
+use sdl2::{pixels::Color, render::Texture as TextureRaw};
+
+use crate::prelude::{MgiResult, Rect};
+
+pub(crate) struct PixelData {
+    pub(crate) pixels: Vec<u8>,
+
+    /// The region that changed since the pixels were last uploaded to the texture
+    pub(crate) dirty: Option<Rect>,
+}
+
+/// A texture whose pixels are written directly by the game, e.g. for emulators or software
+/// rendered effects.
+///
+/// The pixels are stored as RGBA bytes, row by row. Only the regions that changed are uploaded to
+/// the texture, once per frame before the layers are drawn. The texture is registered in the
+/// `TextureManager` under the buffer's name, so it can be drawn like any other texture.
+///
+/// The buffer is shared (cloning it is cheap), so a clone can be kept in the game.
+#[derive(Clone)]
+pub struct PixelBuffer {
+    pub(crate) name: String,
+    pub(crate) size: (u32, u32),
+    pub(crate) data: Rc<RefCell<PixelData>>,
+}
+
+impl PixelBuffer {
+    pub(crate) fn new(name: &str, size: (u32, u32)) -> Self {
+        Self {
+            name: name.into(),
+            size,
+            data: Rc::new(RefCell::new(PixelData {
+                pixels: vec![0; size.0 as usize * size.1 as usize * 4],
+                dirty: Some(Rect::new((0, 0), size.0, size.1)),
+            })),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Returns `None` if the pixel is out of bounds
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let idx = self.index(x, y)?;
+        let data = self.data.borrow();
+        let p = &data.pixels[idx..idx + 4];
+
+        Some(Color::RGBA(p[0], p[1], p[2], p[3]))
+    }
+
+    /// Pixels that are out of bounds are ignored
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if let Some(idx) = self.index(x, y) {
+            let mut data = self.data.borrow_mut();
+            data.pixels[idx..idx + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            Self::mark_dirty(&mut data, Rect::new((x as i32, y as i32), 1, 1));
+        }
+    }
+
+    pub fn fill(&mut self, color: Color) {
+        let mut pixels = self.pixels_mut();
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    pub fn pixels(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.data.borrow(), |data| data.pixels.as_slice())
+    }
+
+    /// Gives access to all of the pixels, so the whole buffer is uploaded again
+    pub fn pixels_mut(&mut self) -> RefMut<'_, [u8]> {
+        let region = Rect::new((0, 0), self.size.0, self.size.1);
+        self.region_mut(region)
+    }
+
+    /// Gives access to all of the pixels, but only uploads `region` again, so only the pixels in
+    /// it should be changed
+    pub fn region_mut(&mut self, region: Rect) -> RefMut<'_, [u8]> {
+        let mut data = self.data.borrow_mut();
+        if let Some(region) = region.intersection(&Rect::new((0, 0), self.size.0, self.size.1)) {
+            Self::mark_dirty(&mut data, region);
+        }
+
+        RefMut::map(data, |data| data.pixels.as_mut_slice())
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.size.0 && y < self.size.1)
+            .then_some((y as usize * self.size.0 as usize + x as usize) * 4)
+    }
+
+    fn mark_dirty(data: &mut PixelData, region: Rect) {
+        data.dirty = Some(match data.dirty {
+            Some(dirty) => dirty.union(&region),
+            None => region,
+        });
+    }
+
+    /// Copies the region that changed into the streaming texture
+    pub(crate) fn upload(&self, raw: &mut TextureRaw) -> MgiResult<()> {
+        let mut data = self.data.borrow_mut();
+        let dirty = match data.dirty.take() {
+            Some(dirty) => dirty,
+            None => return Ok(()),
+        };
+
+        let row_len = self.size.0 as usize * 4;
+        let (left, width) = (dirty.x as usize * 4, dirty.width as usize * 4);
+
+        raw.with_lock(Some(dirty.into()), |buffer, pitch| {
+            for row in 0..dirty.height as usize {
+                let start = (dirty.y as usize + row) * row_len + left;
+                buffer[row * pitch..row * pitch + width]
+                    .copy_from_slice(&data.pixels[start..start + width]);
+            }
+        })?;
+
+        Ok(())
+    }
+}
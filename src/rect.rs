@@ -0,0 +1,167 @@
+use crate::prelude::{Vec2, Vec2f};
+
+/// An axis-aligned rectangle, in pixels.
+///
+/// This is only geometry; use a [`Rectangle`](crate::drawable::Rectangle) to draw one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// The position is the top-left corner of the rectangle
+    pub fn new(position: impl Into<Vec2>, width: u32, height: u32) -> Self {
+        let position = position.into();
+
+        Self {
+            x: position.x,
+            y: position.y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates the smallest rectangle containing both corners
+    pub fn from_corners(a: Vec2, b: Vec2) -> Self {
+        let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+        let (top, bottom) = (a.y.min(b.y), a.y.max(b.y));
+
+        Self::new((left, top), (right - left) as u32, (bottom - top) as u32)
+    }
+
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn left(&self) -> i32 {
+        self.x
+    }
+
+    pub fn top(&self) -> i32 {
+        self.y
+    }
+
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    pub fn center(&self) -> Vec2f {
+        Vec2f::new(
+            self.x as f32 + self.width as f32 / 2.,
+            self.y as f32 + self.height as f32 / 2.,
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vec2f>) -> bool {
+        let point = point.into();
+
+        point.x >= self.left() as f32
+            && point.x < self.right() as f32
+            && point.y >= self.top() as f32
+            && point.y < self.bottom() as f32
+    }
+
+    /// Checks if the rectangles overlap (rectangles that only share an edge don't overlap)
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The area covered by both rectangles
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        if left < right && top < bottom {
+            Some(Rect::from_corners(
+                Vec2::new(left, top),
+                Vec2::new(right, bottom),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// The smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::from_corners(
+            Vec2::new(self.left().min(other.left()), self.top().min(other.top())),
+            Vec2::new(
+                self.right().max(other.right()),
+                self.bottom().max(other.bottom()),
+            ),
+        )
+    }
+
+    /// Grows the rectangle by `dx` on the left and right, and `dy` on the top and bottom (negative
+    /// values shrink it)
+    pub fn inflate(&self, dx: i32, dy: i32) -> Rect {
+        let width = (self.width as i32 + 2 * dx).max(0) as u32;
+        let height = (self.height as i32 + 2 * dy).max(0) as u32;
+
+        Rect::new((self.x - dx, self.y - dy), width, height)
+    }
+
+    /// Splits the rectangle into a left and right part, `at` pixels from the left
+    pub fn split_horizontal(&self, at: u32) -> (Rect, Rect) {
+        let at = at.min(self.width);
+
+        (
+            Rect::new(self.position(), at, self.height),
+            Rect::new((self.x + at as i32, self.y), self.width - at, self.height),
+        )
+    }
+
+    /// Splits the rectangle into a top and bottom part, `at` pixels from the top
+    pub fn split_vertical(&self, at: u32) -> (Rect, Rect) {
+        let at = at.min(self.height);
+
+        (
+            Rect::new(self.position(), self.width, at),
+            Rect::new((self.x, self.y + at as i32), self.width, self.height - at),
+        )
+    }
+
+    /// Moves the rectangle so it lies inside of `bounds` (it is aligned with the left/top of
+    /// `bounds` if it is too big to fit)
+    pub fn clamp(&self, bounds: &Rect) -> Rect {
+        let x = self
+            .x
+            .min(bounds.right() - self.width as i32)
+            .max(bounds.left());
+        let y = self
+            .y
+            .min(bounds.bottom() - self.height as i32)
+            .max(bounds.top());
+
+        Rect::new((x, y), self.width, self.height)
+    }
+}
+
+impl From<Rect> for sdl2::rect::Rect {
+    fn from(r: Rect) -> Self {
+        sdl2::rect::Rect::new(r.x, r.y, r.width, r.height)
+    }
+}
+
+impl From<sdl2::rect::Rect> for Rect {
+    fn from(r: sdl2::rect::Rect) -> Self {
+        Rect::new((r.x(), r.y()), r.width(), r.height())
+    }
+}
@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+
+use sdl2::{
+    pixels::Color,
+    render::{Canvas, Texture as TextureRaw},
+    sys::{SDL_GetRenderTarget, SDL_SetRenderTarget},
+    video::Window,
+};
+
+use crate::prelude::MgiResult;
+
+/// An off-screen texture that can be drawn into, and then drawn like any other texture.
+///
+/// The texture is registered in the `TextureManager` under the target's name, so it can be drawn
+/// using a [`Sprite`](crate::sprite::Sprite) or `Context::draw_texture`.
+#[derive(Clone)]
+pub struct RenderTarget {
+    pub(crate) name: String,
+    pub(crate) size: (u32, u32),
+    pub(crate) clear_color: Color,
+}
+
+impl RenderTarget {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// The color the target is cleared to before it is drawn into (transparent by default)
+    pub fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = color;
+    }
+}
+
+/// Directs all draws on the canvas into `raw` for the duration of `f`, restoring the previous
+/// target after.
+pub(crate) fn with_render_target<R>(
+    canvas: &RefCell<Canvas<Window>>,
+    raw: &TextureRaw,
+    clear_color: Color,
+    f: impl FnOnce() -> MgiResult<R>,
+) -> MgiResult<R> {
+    let renderer = canvas.borrow().raw();
+
+    let prev = unsafe { SDL_GetRenderTarget(renderer) };
+    if unsafe { SDL_SetRenderTarget(renderer, raw.raw()) } != 0 {
+        return Err(sdl2::get_error().into());
+    }
+
+    {
+        let mut canvas = canvas.borrow_mut();
+        let draw_color = canvas.draw_color();
+        canvas.set_draw_color(clear_color);
+        canvas.clear();
+        canvas.set_draw_color(draw_color);
+    }
+
+    // NOTE: The canvas must not be borrowed here, since the drawables borrow it themselves
+    let res = f();
+
+    unsafe {
+        SDL_SetRenderTarget(renderer, prev);
+    }
+
+    res
+}
@@ -1,10 +1,18 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::prelude::{TextureManager, TileMap};
+use crate::{
+    handle::Assets,
+    prelude::{TextureManager, TileMap},
+    vfs::Vfs,
+};
 
 pub(crate) struct ResourceManager {
     pub(crate) texture_manager: Option<Rc<RefCell<TextureManager>>>,
-    pub(crate) tilemap_manager: Option<Rc<RefCell<Vec<TileMap>>>>,
+    pub(crate) tilemap_manager: Option<Rc<RefCell<Assets<TileMap>>>>,
+    pub(crate) vfs: Vfs,
+
+    /// The paths of the manifests that were loaded, to reload them when hot reloading
+    pub(crate) manifests: Vec<String>,
 }
 
 impl Clone for ResourceManager {
@@ -24,6 +32,8 @@ impl Clone for ResourceManager {
         Self {
             texture_manager,
             tilemap_manager,
+            vfs: self.vfs.clone(),
+            manifests: self.manifests.clone(),
         }
     }
 }
@@ -31,11 +41,13 @@ impl Clone for ResourceManager {
 impl ResourceManager {
     pub(crate) fn new(
         texture_manager: Option<Rc<RefCell<TextureManager>>>,
-        tilemap_manager: Option<Rc<RefCell<Vec<TileMap>>>>,
+        tilemap_manager: Option<Rc<RefCell<Assets<TileMap>>>>,
     ) -> Self {
         Self {
             texture_manager,
             tilemap_manager,
+            vfs: Vfs::new(),
+            manifests: Vec::new(),
         }
     }
 }
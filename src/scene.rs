@@ -0,0 +1,295 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    drawable::{Drawable, Rectangle},
+    prelude::*,
+    transform::{Matrix2D, Transform2D},
+};
+
+/// A drawable that can be placed in a `SceneGraph`, drawn relative to its node's world transform
+pub trait SceneDrawable {
+    fn draw_transformed(&mut self, ctx: &Context, transform: &Matrix2D) -> MgiResult<()>;
+}
+
+/// Rectangles can't be rotated or skewed, so they are drawn as the bounding box of the transformed
+/// rectangle.
+impl SceneDrawable for Rectangle {
+    fn draw_transformed(&mut self, ctx: &Context, transform: &Matrix2D) -> MgiResult<()> {
+        let rect = self.rect();
+        let corners = [
+            (rect.left(), rect.top()),
+            (rect.right(), rect.top()),
+            (rect.left(), rect.bottom()),
+            (rect.right(), rect.bottom()),
+        ]
+        .map(|corner| transform.transform_point(corner.into()));
+
+        let min = corners.iter().fold(corners[0], |min, c| {
+            Vec2f::new(min.x.min(c.x), min.y.min(c.y))
+        });
+        let max = corners.iter().fold(corners[0], |max, c| {
+            Vec2f::new(max.x.max(c.x), max.y.max(c.y))
+        });
+
+        let mut rectangle = self.clone();
+        rectangle.set_rect(Rect::from_corners(min.into(), max.into()));
+        rectangle.draw(ctx)
+    }
+}
+
+/// Identifies a node in a `SceneGraph`.
+///
+/// Like a [`Handle`](crate::handle::Handle), it knows when its node has been removed: using it
+/// afterwards is an error, even if another node has been added in its place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u32,
+}
+
+struct Node {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    transform: Transform2D,
+    drawable: Option<Box<dyn SceneDrawable>>,
+    visible: bool,
+}
+
+struct NodeSlot {
+    generation: u32,
+    node: Option<Node>,
+}
+
+/// A hierarchy of nodes, where each node's transform is relative to its parent's.
+///
+/// The graph is shared (cloning it is cheap), so it can be kept in the game and submitted to a
+/// layer every frame with `Context::draw_scene`. The world transforms are computed when the layer
+/// is rendered, and nodes are drawn parents first.
+#[derive(Clone, Default)]
+pub struct SceneGraph {
+    nodes: Rc<RefCell<Vec<NodeSlot>>>,
+}
+
+impl SceneGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node as a child of `parent` (or as a root node if `parent` is `None`)
+    pub fn add_node(
+        &mut self,
+        parent: Option<NodeId>,
+        transform: Transform2D,
+    ) -> MgiResult<NodeId> {
+        let mut nodes = self.nodes.borrow_mut();
+
+        // Checked first, so that a bad parent doesn't leave an empty slot behind
+        if let Some(parent) = parent {
+            Self::node(&nodes, parent)?;
+        }
+
+        // Reuse the slots of removed nodes
+        let index = nodes
+            .iter()
+            .position(|slot| slot.node.is_none())
+            .unwrap_or(nodes.len());
+        if index == nodes.len() {
+            nodes.push(NodeSlot {
+                generation: 0,
+                node: None,
+            });
+        }
+        let id = NodeId {
+            index,
+            generation: nodes[index].generation,
+        };
+
+        if let Some(parent) = parent {
+            Self::node_mut(&mut nodes, parent)?.children.push(id);
+        }
+
+        nodes[index].node = Some(Node {
+            parent,
+            children: Vec::new(),
+            transform,
+            drawable: None,
+            visible: true,
+        });
+
+        Ok(id)
+    }
+
+    /// Removes the node and all of its descendants
+    pub fn remove_node(&mut self, id: NodeId) -> MgiResult<()> {
+        let mut nodes = self.nodes.borrow_mut();
+
+        if let Some(parent) = Self::node_mut(&mut nodes, id)?.parent {
+            Self::node_mut(&mut nodes, parent)?
+                .children
+                .retain(|child| *child != id);
+        }
+
+        let mut to_remove = vec![id];
+        while let Some(id) = to_remove.pop() {
+            let slot = &mut nodes[id.index];
+            if let Some(node) = slot.node.take() {
+                slot.generation += 1;
+                to_remove.extend(node.children);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn set_drawable<T: SceneDrawable + 'static>(
+        &mut self,
+        id: NodeId,
+        drawable: T,
+    ) -> MgiResult<()> {
+        Self::node_mut(&mut self.nodes.borrow_mut(), id)?.drawable = Some(Box::new(drawable));
+        Ok(())
+    }
+
+    pub fn set_transform(&mut self, id: NodeId, transform: Transform2D) -> MgiResult<()> {
+        Self::node_mut(&mut self.nodes.borrow_mut(), id)?.transform = transform;
+        Ok(())
+    }
+
+    /// Hidden nodes, and all of their descendants, aren't drawn
+    pub fn set_visible(&mut self, id: NodeId, visible: bool) -> MgiResult<()> {
+        Self::node_mut(&mut self.nodes.borrow_mut(), id)?.visible = visible;
+        Ok(())
+    }
+
+    /// The node's transform, relative to its parent
+    pub fn transform(&self, id: NodeId) -> MgiResult<Transform2D> {
+        Ok(Self::node(&self.nodes.borrow(), id)?.transform)
+    }
+
+    /// The node's transform, relative to the screen
+    pub fn world_matrix(&self, id: NodeId) -> MgiResult<Matrix2D> {
+        let nodes = self.nodes.borrow();
+
+        let mut matrix = Matrix2D::IDENTITY;
+        let mut current = Some(id);
+        while let Some(id) = current {
+            let node = Self::node(&nodes, id)?;
+            matrix = node.transform.matrix() * matrix;
+            current = node.parent;
+        }
+
+        Ok(matrix)
+    }
+
+    fn node(nodes: &[NodeSlot], id: NodeId) -> MgiResult<&Node> {
+        match nodes.get(id.index) {
+            Some(slot) if slot.generation == id.generation => slot.node.as_ref(),
+            Some(_) => return Err(Self::stale(id)),
+            None => None,
+        }
+        .ok_or_else(|| format!("The scene node {:?} does not exist", id).into())
+    }
+
+    fn node_mut(nodes: &mut [NodeSlot], id: NodeId) -> MgiResult<&mut Node> {
+        match nodes.get_mut(id.index) {
+            Some(slot) if slot.generation == id.generation => slot.node.as_mut(),
+            Some(_) => return Err(Self::stale(id)),
+            None => None,
+        }
+        .ok_or_else(|| format!("The scene node {:?} does not exist", id).into())
+    }
+
+    fn stale(id: NodeId) -> Box<dyn std::error::Error> {
+        format!("The scene node {:?} is stale: it was removed", id).into()
+    }
+}
+
+/// Draws every visible node of a `SceneGraph`, submitted to a layer by `Context::draw_scene`
+pub(crate) struct SceneBatch {
+    pub(crate) scene: SceneGraph,
+}
+
+impl Drawable for SceneBatch {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        let mut nodes = self.scene.nodes.borrow_mut();
+
+        // Walk down from the root nodes, propagating the world transforms to the children
+        let mut stack: Vec<(NodeId, Matrix2D)> = nodes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(|(index, slot)| match &slot.node {
+                Some(node) if node.parent.is_none() => {
+                    let id = NodeId {
+                        index,
+                        generation: slot.generation,
+                    };
+                    Some((id, Matrix2D::IDENTITY))
+                }
+                _ => None,
+            })
+            .collect();
+
+        while let Some((id, parent_matrix)) = stack.pop() {
+            let node = SceneGraph::node_mut(&mut nodes, id)?;
+            if !node.visible {
+                continue;
+            }
+
+            let matrix = parent_matrix * node.transform.matrix();
+            if let Some(drawable) = &mut node.drawable {
+                drawable.draw_transformed(ctx, &matrix)?;
+            }
+
+            stack.extend(node.children.iter().rev().map(|child| (*child, matrix)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(scene: &mut SceneGraph, parent: Option<NodeId>) -> MgiResult<NodeId> {
+        scene.add_node(parent, Transform2D::new((0., 0.)))
+    }
+
+    #[test]
+    fn removed_nodes_are_stale() {
+        let mut scene = SceneGraph::new();
+        let root = add(&mut scene, None).unwrap();
+        let child = add(&mut scene, Some(root)).unwrap();
+
+        scene.remove_node(root).unwrap();
+        for id in [root, child] {
+            let e = scene.transform(id).unwrap_err();
+            assert!(e.to_string().contains("is stale"), "{}", e);
+        }
+
+        // The slots are reused, but the old ids still don't refer to the new nodes
+        let new_root = add(&mut scene, None).unwrap();
+        let new_child = add(&mut scene, Some(new_root)).unwrap();
+        assert_eq!(new_root.index, root.index);
+        assert_ne!(new_root, root);
+        assert!(scene.set_visible(root, false).is_err());
+        assert!(scene.remove_node(child).is_err());
+        assert!(scene.transform(new_child).is_ok());
+    }
+
+    #[test]
+    fn bad_parents_leave_no_slot_behind() {
+        let mut scene = SceneGraph::new();
+        let root = add(&mut scene, None).unwrap();
+        scene.remove_node(root).unwrap();
+
+        let missing = NodeId {
+            index: 10,
+            generation: 0,
+        };
+        assert!(add(&mut scene, Some(root)).is_err());
+        assert!(add(&mut scene, Some(missing)).is_err());
+        assert_eq!(scene.nodes.borrow().len(), 1);
+    }
+}
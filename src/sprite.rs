@@ -0,0 +1,167 @@
+use sdl2::{pixels::Color, rect::Point};
+
+use crate::{
+    blend::with_texture_blend_mode, drawable::Drawable, prelude::*, scene::SceneDrawable,
+    texture_manager::with_tint, transform::Matrix2D,
+};
+
+/// A texture from the `TextureManager` drawn with its own transform and tint.
+///
+/// The `position` is where the `origin` (pivot) of the sprite is placed, and rotation is
+/// performed about that same pivot.
+#[derive(Clone)]
+pub struct Sprite {
+    texture: AssetKey<Texture>,
+    position: Vec2f,
+    scale: Vec2f,
+    origin: Vec2f,
+    rotation: Rotation,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+    pub(crate) color_tint: Option<Color>,
+    pub(crate) alpha_tint: Option<f32>,
+    src: Option<Rect>,
+    pub(crate) blend_mode: Option<BlendMode>,
+}
+
+impl Sprite {
+    pub fn new(texture: impl Into<AssetKey<Texture>>) -> Self {
+        Self {
+            texture: texture.into(),
+            position: Vec2f::ZERO,
+            scale: Vec2f::new(1., 1.),
+            origin: Vec2f::ZERO,
+            rotation: Rotation::Radians(0.),
+            flip_horizontal: false,
+            flip_vertical: false,
+            color_tint: None,
+            alpha_tint: None,
+            src: None,
+            blend_mode: None,
+        }
+    }
+
+    pub fn position(mut self, position: impl Into<Vec2f>) -> Self {
+        self.position = position.into();
+        self
+    }
+
+    pub fn scale(mut self, scale: impl Into<Vec2f>) -> Self {
+        self.scale = scale.into();
+        self
+    }
+
+    /// The pivot of the sprite, relative to its (unscaled) top-left corner
+    pub fn origin(mut self, origin: impl Into<Vec2f>) -> Self {
+        self.origin = origin.into();
+        self
+    }
+
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn flip(mut self, horizontal: bool, vertical: bool) -> Self {
+        self.flip_horizontal = horizontal;
+        self.flip_vertical = vertical;
+        self
+    }
+
+    pub fn color_tint(mut self, color: Color) -> Self {
+        self.color_tint = Some(color);
+        self
+    }
+
+    /// Alpha in the range `0.0..=1.0`
+    pub fn alpha_tint(mut self, alpha: f32) -> Self {
+        self.alpha_tint = Some(alpha);
+        self
+    }
+
+    /// The region of the texture to draw (the whole texture is used if this isn't set)
+    pub fn src(mut self, src: Rect) -> Self {
+        self.src = Some(src);
+        self
+    }
+
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+}
+
+impl Sprite {
+    /// The transform from the sprite's texture pixels to its position on the screen
+    fn matrix(&self) -> Matrix2D {
+        Matrix2D::translation(self.position)
+            * Matrix2D::rotation(self.rotation)
+            * Matrix2D::scale(self.scale)
+            * Matrix2D::translation(-self.origin)
+    }
+}
+
+impl Drawable for Sprite {
+    fn draw(&mut self, ctx: &Context) -> MgiResult<()> {
+        self.draw_transformed(ctx, &Matrix2D::IDENTITY)
+    }
+}
+
+impl SceneDrawable for Sprite {
+    fn draw_transformed(&mut self, ctx: &Context, transform: &Matrix2D) -> MgiResult<()> {
+        let region = match ctx.resolve_texture(&self.texture)? {
+            Some(region) => region,
+            None => return Ok(()),
+        };
+        let src = region.src(self.src);
+        if src.is_some_and(|src| src.is_empty()) {
+            return Ok(());
+        }
+
+        // The natural size of the sprite is the size of the region being drawn
+        let (width, height) = if let Some(src) = &self.src {
+            (src.width, src.height)
+        } else {
+            region.size()
+        };
+
+        // SDL can only rotate about a point and scale, so any skew is lost here
+        let (top_left, rotation, scale) = (*transform * self.matrix()).decompose();
+        let size = Vec2f::new(width as f32 * scale.x.abs(), height as f32 * scale.y.abs());
+
+        // Negative scales are drawn as flips, which extend the sprite in the other direction from
+        // its (rotated) top-left corner
+        let mut pivot = Vec2f::ZERO;
+        if scale.x < 0. {
+            pivot.x = size.x;
+        }
+        if scale.y < 0. {
+            pivot.y = size.y;
+        }
+
+        let dest_pos = Vec2::from(top_left - pivot);
+        let dest = sdl2::rect::Rect::new(
+            dest_pos.x,
+            dest_pos.y,
+            size.x.round() as u32,
+            size.y.round() as u32,
+        );
+        let pivot = Point::new(pivot.x.round() as i32, pivot.y.round() as i32);
+        let src = src.map(sdl2::rect::Rect::from);
+        let mut raw = region.raw.borrow_mut();
+
+        with_texture_blend_mode(&mut raw, self.blend_mode, |raw| {
+            Ok(with_tint(raw, self.color_tint, self.alpha_tint, |raw| {
+                ctx.canvas().borrow_mut().copy_ex(
+                    raw,
+                    src,
+                    dest,
+                    rotation.to_degrees() as f64,
+                    pivot,
+                    self.flip_horizontal != (scale.x < 0.),
+                    self.flip_vertical != (scale.y < 0.),
+                )
+            })?)
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use resvg::{tiny_skia, usvg};
+
+use crate::prelude::MgiResult;
+
+pub(crate) fn is_svg(path: &str) -> bool {
+    path.to_lowercase().ends_with(".svg")
+}
+
+/// Rasterizes the SVG file (read from `path` into `data`) into RGBA pixels (that aren't
+/// premultiplied by alpha).
+///
+/// The image is rendered at `size` (or at the size declared by the SVG if it's `None`), multiplied
+/// by `scale`. Returns the size of the rasterized image along with its pixels.
+pub(crate) fn rasterize(
+    path: &str,
+    data: &[u8],
+    size: Option<(u32, u32)>,
+    scale: f32,
+) -> MgiResult<((u32, u32), Vec<u8>)> {
+    let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+        .map_err(|e| format!("Couldn't parse `{}`: {}", path, e))?;
+
+    let svg_size = tree.size();
+    let (width, height) = size.unwrap_or((
+        svg_size.width().ceil() as u32,
+        svg_size.height().ceil() as u32,
+    ));
+    let (width, height) = (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    );
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| format!("Couldn't rasterize `{}` at {}x{}", path, width, height))?;
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / svg_size.width(),
+        height as f32 / svg_size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    Ok(((width, height), pixels))
+}
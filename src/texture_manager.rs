@@ -1,23 +1,170 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use sdl2::{
     image::LoadTexture,
+    pixels::{Color, PixelFormatEnum},
     render::{Texture as TextureRaw, TextureCreator},
     video::WindowContext,
 };
 
 use crate::{
-    drawable::{Drawable, Rectangle},
-    prelude::{Context, MgiResult, Rotation},
+    atlas::{Atlas, AtlasOptions},
+    blend::with_texture_blend_mode,
+    drawable::Drawable,
+    handle::{AssetKey, Assets, Handle},
+    image::Image,
+    missing_asset,
+    prelude::{BlendMode, Context, MgiResult, Rect, Rotation, Vec2f},
+    svg,
+    vfs::Vfs,
 };
 
-pub(crate) struct Texture {
+/// A texture managed by the `TextureManager`, referred to by its name or a `Handle<Texture>`
+pub struct Texture {
     pub(crate) name: String,
     pub(crate) path: String,
     pub(crate) raw: Option<Rc<RefCell<TextureRaw>>>,
-    pub(crate) src: Option<Rectangle>,
-    pub(crate) dest: Option<Rectangle>,
+
+    /// The part of `raw` that is this texture, when it was packed into an atlas
+    pub(crate) region: Option<Rect>,
+
+    pub(crate) src: Option<Rect>,
+    pub(crate) dest: Option<Rect>,
     pub(crate) rotation: Rotation,
+    pub(crate) color_tint: Option<Color>,
+    pub(crate) alpha_tint: Option<f32>,
+    pub(crate) blend_mode: Option<BlendMode>,
+}
+
+impl Texture {
+    pub(crate) fn new(name: &str, path: &str, raw: Option<TextureRaw>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            raw: raw.map(|raw| Rc::new(RefCell::new(raw))),
+            region: None,
+            src: None,
+            dest: None,
+            rotation: Rotation::Radians(0.0),
+            color_tint: None,
+            alpha_tint: None,
+            blend_mode: None,
+        }
+    }
+}
+
+/// The size, pixel format and memory usage of a loaded texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureInfo {
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormatEnum,
+
+    /// The memory used by the pixels of the texture, in bytes
+    pub memory: usize,
+}
+
+impl TextureInfo {
+    /// For textures packed into an atlas, only the pixels of their region are counted
+    fn new(texture: &TextureRegion) -> Self {
+        let format = texture.raw.borrow().query().format;
+        let (width, height) = texture.size();
+
+        Self {
+            width,
+            height,
+            format,
+            memory: format.byte_size_of_pixels(width as usize * height as usize),
+        }
+    }
+}
+
+/// The raw texture to draw a texture with, and the part of it that is the texture
+#[derive(Clone)]
+pub(crate) struct TextureRegion {
+    pub(crate) raw: Rc<RefCell<TextureRaw>>,
+
+    /// `None` if the texture is the whole raw texture
+    pub(crate) rect: Option<Rect>,
+}
+
+impl TextureRegion {
+    pub(crate) fn whole(raw: Rc<RefCell<TextureRaw>>) -> Self {
+        Self { raw, rect: None }
+    }
+
+    pub(crate) fn size(&self) -> (u32, u32) {
+        match self.rect {
+            Some(rect) => rect.size(),
+            None => {
+                let query = self.raw.borrow().query();
+                (query.width, query.height)
+            }
+        }
+    }
+
+    /// Turns a region of the texture (or the whole texture) into a region of the raw texture.
+    ///
+    /// The region is clipped to the texture, so it never samples the textures next to it in an
+    /// atlas; it is empty if it is entirely outside of the texture, and nothing should be drawn.
+    pub(crate) fn src(&self, src: Option<Rect>) -> Option<Rect> {
+        match (self.rect, src) {
+            (None, src) => src,
+            (Some(rect), None) => Some(rect),
+            (Some(rect), Some(src)) => {
+                let src = Rect::new((rect.x + src.x, rect.y + src.y), src.width, src.height);
+                Some(
+                    src.intersection(&rect)
+                        .unwrap_or_else(|| Rect::new(rect.position(), 0, 0)),
+                )
+            }
+        }
+    }
+
+    /// Turns normalized texture coordinates of the texture into ones of the raw texture
+    pub(crate) fn uv(&self, uv: Vec2f) -> Vec2f {
+        match self.rect {
+            Some(rect) => {
+                let query = self.raw.borrow().query();
+                Vec2f::new(
+                    (rect.x as f32 + uv.x * rect.width as f32) / query.width as f32,
+                    (rect.y as f32 + uv.y * rect.height as f32) / query.height as f32,
+                )
+            }
+            None => uv,
+        }
+    }
+}
+
+/// Applies the tint to `raw` only for the duration of `f`, restoring the previous tint after.
+///
+/// The raw textures are shared by every draw of the same texture, so the tint can't be left set.
+pub(crate) fn with_tint<R>(
+    raw: &mut TextureRaw,
+    color_tint: Option<Color>,
+    alpha_tint: Option<f32>,
+    f: impl FnOnce(&TextureRaw) -> R,
+) -> R {
+    let (r, g, b) = raw.color_mod();
+    let a = raw.alpha_mod();
+
+    if let Some(color) = color_tint {
+        raw.set_color_mod(color.r, color.g, color.b);
+    }
+    if let Some(alpha) = alpha_tint {
+        raw.set_alpha_mod((255. * alpha) as u8);
+    }
+
+    let res = f(raw);
+
+    raw.set_color_mod(r, g, b);
+    raw.set_alpha_mod(a);
+
+    res
 }
 
 impl Drawable for Texture {
@@ -35,82 +182,625 @@ impl Drawable for Texture {
         // Get raw texture
         let raw = self.raw.as_ref().unwrap();
 
-        // Get source if it exists
-        let src = if let Some(src) = &self.src {
-            let src: sdl2::rect::Rect = src.into();
-            Some(src)
-        } else {
-            None
-        };
+        let src = self.src.map(sdl2::rect::Rect::from);
+        let dest = self.dest.map(sdl2::rect::Rect::from);
 
-        // Get destination if it exists
-        let dest = if let Some(src) = &self.dest {
-            let src: sdl2::rect::Rect = src.into();
-            Some(src)
-        } else {
-            None
-        };
+        with_texture_blend_mode(&mut raw.borrow_mut(), self.blend_mode, |raw| {
+            Ok(with_tint(raw, self.color_tint, self.alpha_tint, |raw| {
+                canvas.borrow_mut().copy_ex(
+                    raw,
+                    src,
+                    dest,
+                    self.rotation.to_degrees() as f64,
+                    None,
+                    false,
+                    false,
+                )
+            })?)
+        })
+    }
+}
 
-        canvas.borrow_mut().copy_ex(
-            &raw.borrow(),
-            src,
-            dest,
-            self.rotation.to_degrees() as f64,
-            None,
-            false,
-            false,
-        )?;
+/// Where a texture is loaded from, when it isn't an image file on disk
+#[derive(Clone)]
+pub(crate) enum TextureSource {
+    /// An SVG file, rasterized at the given size (or at the size declared by the SVG)
+    Svg(Option<(u32, u32)>),
 
-        Ok(())
-    }
+    /// An encoded image file that is already in memory
+    Bytes(Vec<u8>),
+
+    /// RGBA pixels, row by row
+    Rgba((u32, u32), Vec<u8>),
 }
 
 pub struct TextureManager {
-    pub(crate) textures: Vec<Texture>,
+    pub(crate) textures: Assets<Texture>,
 
     // Used to create the texture
     pub(crate) texture_creator: Option<TextureCreator<WindowContext>>,
+
+    /// The sources of the textures that aren't loaded from an image file, by texture name
+    sources: HashMap<String, TextureSource>,
+    svg_scale: f32,
+
+    /// Where the textures are read from, set when the game starts
+    pub(crate) vfs: Vfs,
+
+    atlas: Option<AtlasOptions>,
+
+    /// Raw textures of unloaded or replaced textures, destroyed once nothing uses them anymore
+    released: Vec<Rc<RefCell<TextureRaw>>>,
+
+    /// The textures being loaded in the background
+    loading: HashSet<Handle<Texture>>,
 }
 
 impl TextureManager {
     pub fn new() -> Self {
         Self {
-            textures: Vec::new(),
+            textures: Assets::new("texture"),
             texture_creator: None,
+            sources: HashMap::new(),
+            svg_scale: 1.,
+            vfs: Vfs::new(),
+            atlas: None,
+            released: Vec::new(),
+            loading: HashSet::new(),
         }
     }
 
-    pub fn add_texture(&mut self, name: &str, path: &str) {
-        self.textures.push(Texture {
-            name: name.into(),
-            path: path.into(),
-            raw: None,
-            src: None,
-            dest: None,
-            rotation: Rotation::Radians(0.0),
-        });
+    /// Adds an image to be loaded when the game starts.
+    ///
+    /// `.svg` files are rasterized at the size declared by the SVG; use `add_svg_texture` to
+    /// rasterize them at another size.
+    /// Adding a texture with the same name as another replaces it.
+    pub fn add_texture(&mut self, name: &str, path: &str) -> Handle<Texture> {
+        self.sources.remove(name);
+        if svg::is_svg(path) {
+            self.sources.insert(name.into(), TextureSource::Svg(None));
+        }
+
+        self.textures.insert(name, Texture::new(name, path, None))
+    }
+
+    /// Adds an SVG image, rasterized at `size` (multiplied by the SVG scale, see
+    /// `Context::set_svg_scale`)
+    pub fn add_svg_texture(&mut self, name: &str, path: &str, size: (u32, u32)) -> Handle<Texture> {
+        let handle = self.add_texture(name, path);
+        self.sources
+            .insert(name.into(), TextureSource::Svg(Some(size)));
+
+        handle
+    }
+
+    /// Adds an encoded image file that is already in memory (e.g. embedded with `include_bytes!`)
+    pub fn add_texture_from_bytes(&mut self, name: &str, bytes: &[u8]) -> Handle<Texture> {
+        let handle = self.add_texture(name, "");
+        self.sources
+            .insert(name.into(), TextureSource::Bytes(bytes.to_vec()));
+
+        handle
+    }
+
+    /// Adds a texture from RGBA pixels, row by row
+    pub fn add_texture_from_rgba(
+        &mut self,
+        name: &str,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> MgiResult<Handle<Texture>> {
+        let image = Image::from_rgba(width, height, data.to_vec())?;
+        Ok(self.add_texture_from_image(name, &image))
+    }
+
+    pub fn add_texture_from_image(&mut self, name: &str, image: &Image) -> Handle<Texture> {
+        let handle = self.add_texture(name, "");
+        self.sources.insert(
+            name.into(),
+            TextureSource::Rgba(image.size(), image.pixels().to_vec()),
+        );
+
+        handle
+    }
+
+    /// Packs the textures added to the texture manager into atlases when the game starts, so
+    /// drawing them doesn't need a texture switch each time. The textures are still drawn by
+    /// their names and handles, which refer to their region of the atlas.
+    ///
+    /// SVG textures aren't packed, since they are rasterized again when the SVG scale changes.
+    ///
+    /// NOTE: Textures loaded with `GameBuilder::load_textures_async` or while the game is running
+    /// aren't packed, and textures packed into an atlas aren't hot reloaded
+    pub fn set_atlas(&mut self, options: AtlasOptions) {
+        self.atlas = Some(options);
+    }
+
+    /// The handle of the texture with this name
+    pub fn texture_handle(&self, name: &str) -> Option<Handle<Texture>> {
+        self.textures.handle(name)
+    }
+
+    /// Fails if the texture doesn't exist or isn't loaded yet (textures are loaded when the game
+    /// starts)
+    pub fn texture_info(&self, texture: impl Into<AssetKey<Texture>>) -> MgiResult<TextureInfo> {
+        Ok(TextureInfo::new(&self.get_region(&texture.into())?))
+    }
+
+    /// The width and height of the texture, see `texture_info`
+    pub fn texture_size(&self, texture: impl Into<AssetKey<Texture>>) -> MgiResult<(u32, u32)> {
+        let info = self.texture_info(texture)?;
+        Ok((info.width, info.height))
+    }
+
+    /// The memory used by the pixels of every loaded texture (counting each atlas page once), in
+    /// bytes
+    pub fn memory_usage(&self) -> usize {
+        let mut counted = HashSet::new();
+
+        self.textures
+            .iter()
+            .filter_map(|(_, texture)| texture.raw.as_ref())
+            .filter(|raw| counted.insert(Rc::as_ptr(raw)))
+            .map(|raw| TextureInfo::new(&TextureRegion::whole(Rc::clone(raw))).memory)
+            .sum()
     }
 
     pub(crate) fn load_textures(&mut self) -> MgiResult<()> {
-        for texture in self.textures.iter_mut() {
-            texture.raw = Some(Rc::new(RefCell::new(
-                self.texture_creator
-                    .as_ref()
-                    .unwrap()
-                    .load_texture(&texture.path)?,
-            )));
+        if let Some(options) = self.atlas.clone() {
+            self.pack_atlas(&options)?;
+        }
+
+        let texture_creator = self.texture_creator.as_ref().unwrap();
+
+        // The textures packed into an atlas are already loaded
+        for (_, texture) in self.textures.iter_mut() {
+            if texture.raw.is_some() {
+                continue;
+            }
+
+            let raw = Self::create_raw(
+                texture_creator,
+                &self.vfs,
+                &texture.name,
+                &texture.path,
+                self.sources.get(&texture.name),
+                self.svg_scale,
+            )?;
+            texture.raw = Some(Rc::new(RefCell::new(raw)));
         }
 
         Ok(())
     }
 
-    pub(crate) fn get_texture_mut(&mut self, name: &str) -> Option<&mut Texture> {
-        for texture in &mut self.textures {
-            if texture.name == name {
-                return Some(texture);
+    /// Decodes the textures, packs them into atlas pages, and points each texture to its region
+    fn pack_atlas(&mut self, options: &AtlasOptions) -> MgiResult<()> {
+        let mut images = Vec::new();
+        for (_, texture) in self.textures.iter() {
+            let (name, path) = (&texture.name, &texture.path);
+            let image = match self.sources.get(name) {
+                Some(TextureSource::Svg(_)) => continue,
+                Some(TextureSource::Bytes(bytes)) => Image::from_bytes(bytes)
+                    .map_err(|e| format!("Couldn't decode the texture `{}`: {}", name, e))?,
+                Some(TextureSource::Rgba((width, height), pixels)) => {
+                    Image::from_rgba(*width, *height, pixels.clone())?
+                }
+                None => Image::from_bytes(&self.vfs.read(path)?)
+                    .map_err(|e| format!("Couldn't decode `{}`: {}", path, e))?,
+            };
+            images.push((name.clone(), image));
+        }
+
+        let atlas = Atlas::pack(options, images);
+        if let Some(directory) = &options.dump_directory {
+            atlas.dump(directory)?;
+        }
+
+        let texture_creator = self.texture_creator.as_ref().unwrap();
+        let pages = atlas
+            .pages
+            .iter()
+            .map(|page| {
+                let raw = Self::create_rgba_texture(texture_creator, page.size(), page.pixels())?;
+                Ok(Rc::new(RefCell::new(raw)))
+            })
+            .collect::<MgiResult<Vec<_>>>()?;
+
+        for (name, region) in atlas.regions {
+            let texture = self.textures.get_mut(&name.into())?;
+            texture.raw = Some(Rc::clone(&pages[region.page]));
+            texture.region = Some(region.rect);
+        }
+
+        Ok(())
+    }
+
+    /// Loads a texture while the game is running, replacing the texture with the same name if
+    /// there is one (in which case its handle stays valid).
+    ///
+    /// `source` is `None` for image files, which are rasterized if they are SVGs.
+    pub(crate) fn load_texture(
+        &mut self,
+        name: &str,
+        path: &str,
+        source: Option<TextureSource>,
+    ) -> MgiResult<Handle<Texture>> {
+        // Loaded first, so that the old texture is kept if loading fails
+        let raw = self.decode_texture(name, path, source.clone())?;
+
+        Ok(self.replace_texture(name, path, source, raw))
+    }
+
+    /// Creates the raw texture of a texture loaded while the game is running, see `load_texture`
+    pub(crate) fn decode_texture(
+        &self,
+        name: &str,
+        path: &str,
+        source: Option<TextureSource>,
+    ) -> MgiResult<TextureRaw> {
+        let texture_creator = self
+            .texture_creator
+            .as_ref()
+            .ok_or("Textures can only be loaded once the game is running")?;
+
+        Self::create_raw(
+            texture_creator,
+            &self.vfs,
+            name,
+            path,
+            Self::resolve_source(path, source).as_ref(),
+            self.svg_scale,
+        )
+    }
+
+    /// Replaces the texture with the same name (keeping its handle) or adds it, see
+    /// `load_texture`
+    pub(crate) fn replace_texture(
+        &mut self,
+        name: &str,
+        path: &str,
+        source: Option<TextureSource>,
+        raw: TextureRaw,
+    ) -> Handle<Texture> {
+        let old_raw = match self.textures.get_mut(&name.into()) {
+            Ok(old) => old.raw.take(),
+            Err(_) => None,
+        };
+        if let Some(old_raw) = old_raw {
+            self.release(old_raw);
+        }
+
+        match Self::resolve_source(path, source) {
+            Some(source) => self.sources.insert(name.into(), source),
+            None => self.sources.remove(name),
+        };
+
+        self.textures
+            .insert(name, Texture::new(name, path, Some(raw)))
+    }
+
+    /// Registers a texture that is loaded in the background, keeping the texture with the same
+    /// name (if there is one) until the new one is loaded
+    pub(crate) fn reserve_texture(&mut self, name: &str, path: &str) -> Handle<Texture> {
+        let handle = match self.textures.handle(name) {
+            Some(handle) => handle,
+            None => self.textures.insert(name, Texture::new(name, path, None)),
+        };
+        self.loading.insert(handle);
+
+        handle
+    }
+
+    /// Called once a texture loaded in the background is uploaded, or failed to load
+    pub(crate) fn stop_loading(&mut self, handle: Handle<Texture>) {
+        self.loading.remove(&handle);
+    }
+
+    /// Whether the texture has no raw texture yet because it is still being loaded in the
+    /// background, rather than because it is missing
+    pub(crate) fn is_loading(&self, key: &AssetKey<Texture>) -> bool {
+        let handle = match key {
+            AssetKey::Name(name) => self.textures.handle(name),
+            AssetKey::Handle(handle) => Some(*handle),
+        };
+
+        handle.is_some_and(|handle| self.loading.contains(&handle))
+            && self
+                .get_texture(key)
+                .is_ok_and(|texture| texture.raw.is_none())
+    }
+
+    /// Uploads a texture decoded in the background. Fails if the texture was unloaded meanwhile.
+    pub(crate) fn finish_texture(
+        &mut self,
+        handle: Handle<Texture>,
+        path: &str,
+        source: Option<TextureSource>,
+        image: &Image,
+    ) -> MgiResult<()> {
+        let texture = self.textures.get_mut(&handle.into())?;
+        let raw = Self::create_rgba_texture(
+            self.texture_creator.as_ref().unwrap(),
+            image.size(),
+            image.pixels(),
+        )?;
+
+        texture.path = path.into();
+        texture.region = None;
+        let old_raw = texture.raw.replace(Rc::new(RefCell::new(raw)));
+        let name = texture.name.clone();
+
+        if let Some(old_raw) = old_raw {
+            self.release(old_raw);
+        }
+        match source {
+            Some(source) => self.sources.insert(name, source),
+            None => self.sources.remove(&name),
+        };
+
+        Ok(())
+    }
+
+    /// The names of the textures that haven't been loaded yet, with where to load them from
+    pub(crate) fn unloaded_textures(&self) -> Vec<(String, String, Option<TextureSource>)> {
+        self.textures
+            .iter()
+            .filter(|(_, texture)| texture.raw.is_none())
+            .map(|(_, texture)| {
+                (
+                    texture.name.clone(),
+                    texture.path.clone(),
+                    self.sources.get(&texture.name).cloned(),
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn texture_paths(&self) -> Vec<String> {
+        self.textures
+            .iter()
+            .map(|(_, texture)| texture.path.clone())
+            .collect()
+    }
+
+    pub(crate) fn svg_scale(&self) -> f32 {
+        self.svg_scale
+    }
+
+    /// `source` is `None` for image files, which are rasterized if they are SVGs
+    pub(crate) fn resolve_source(
+        path: &str,
+        source: Option<TextureSource>,
+    ) -> Option<TextureSource> {
+        source.or_else(|| svg::is_svg(path).then_some(TextureSource::Svg(None)))
+    }
+
+    /// Removes the texture, making its handles stale, and frees its raw texture
+    pub(crate) fn unload_texture(&mut self, key: &AssetKey<Texture>) -> MgiResult<Texture> {
+        let mut texture = self.textures.remove(key)?;
+        self.sources.remove(&texture.name);
+
+        if let Some(raw) = texture.raw.take() {
+            self.release(raw);
+        }
+
+        Ok(texture)
+    }
+
+    /// Destroys the raw texture, or keeps it until `destroy_released` if it is still used (e.g. by
+    /// a draw command queued this frame)
+    fn release(&mut self, raw: Rc<RefCell<TextureRaw>>) {
+        match Rc::try_unwrap(raw) {
+            Ok(raw) => unsafe { raw.into_inner().destroy() },
+            // The textures packed into the same atlas page share its raw texture, which only
+            // needs to be kept once
+            Err(raw) => {
+                if !self.released.iter().any(|other| Rc::ptr_eq(other, &raw)) {
+                    self.released.push(raw);
+                }
             }
         }
+    }
+
+    /// Destroys the released raw textures that aren't used anymore, called every frame once the
+    /// layers have been drawn
+    pub(crate) fn destroy_released(&mut self) {
+        for raw in std::mem::take(&mut self.released) {
+            self.release(raw);
+        }
+    }
+
+    /// Re-rasterizes every SVG texture at the new scale
+    pub(crate) fn set_svg_scale(&mut self, scale: f32) -> MgiResult<()> {
+        if scale <= 0. {
+            return Err(format!("The SVG scale must be positive, not {}", scale).into());
+        }
+        if scale == self.svg_scale {
+            return Ok(());
+        }
+        self.svg_scale = scale;
+
+        // Textures are only created once the game is running, until then only the scale is kept
+        let texture_creator = match &self.texture_creator {
+            Some(texture_creator) => texture_creator,
+            None => return Ok(()),
+        };
+
+        for (_, texture) in self.textures.iter() {
+            if let (Some(TextureSource::Svg(size)), Some(raw)) =
+                (self.sources.get(&texture.name), &texture.raw)
+            {
+                let new_raw =
+                    Self::rasterize_svg(texture_creator, &self.vfs, &texture.path, *size, scale)?;
+
+                // The texture is replaced in place, so everything referring to it keeps working
+                let old_raw = std::mem::replace(&mut *raw.borrow_mut(), new_raw);
+                unsafe { old_raw.destroy() };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reloads the textures whose file changed in place, so their names and handles stay valid.
+    ///
+    /// Failures are logged, and the texture keeps its previous data.
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn reload_changed(&self, changed: &std::collections::HashSet<std::path::PathBuf>) {
+        let texture_creator = self.texture_creator.as_ref().unwrap();
+
+        for (_, texture) in self.textures.iter() {
+            // Replacing the raw texture of a packed texture would replace its whole atlas page
+            let raw = match &texture.raw {
+                Some(raw) if !texture.path.is_empty() && texture.region.is_none() => raw,
+                _ => continue,
+            };
+            match self.vfs.disk_path(&texture.path).map(std::fs::canonicalize) {
+                Some(Ok(path)) if changed.contains(&path) => {}
+                _ => continue,
+            }
+
+            let source = self.sources.get(&texture.name);
+            match Self::create_raw(
+                texture_creator,
+                &self.vfs,
+                &texture.name,
+                &texture.path,
+                source,
+                self.svg_scale,
+            ) {
+                Ok(new_raw) => {
+                    let old_raw = std::mem::replace(&mut *raw.borrow_mut(), new_raw);
+                    unsafe { old_raw.destroy() };
+                }
+                Err(e) => eprintln!("Couldn't reload the texture `{}`: {}", texture.name, e),
+            }
+        }
+    }
+
+    fn create_raw(
+        texture_creator: &TextureCreator<WindowContext>,
+        vfs: &Vfs,
+        name: &str,
+        path: &str,
+        source: Option<&TextureSource>,
+        svg_scale: f32,
+    ) -> MgiResult<TextureRaw> {
+        Ok(match source {
+            Some(TextureSource::Svg(size)) => {
+                Self::rasterize_svg(texture_creator, vfs, path, *size, svg_scale)?
+            }
+            Some(TextureSource::Bytes(bytes)) => texture_creator
+                .load_texture_bytes(bytes)
+                .map_err(|e| format!("Couldn't decode the texture `{}`: {}", name, e))?,
+            Some(TextureSource::Rgba(size, pixels)) => {
+                Self::create_rgba_texture(texture_creator, *size, pixels)?
+            }
+            None => texture_creator
+                .load_texture_bytes(&vfs.read(path)?)
+                .map_err(|e| format!("Couldn't decode `{}`: {}", path, e))?,
+        })
+    }
+
+    fn rasterize_svg(
+        texture_creator: &TextureCreator<WindowContext>,
+        vfs: &Vfs,
+        path: &str,
+        size: Option<(u32, u32)>,
+        scale: f32,
+    ) -> MgiResult<TextureRaw> {
+        let (size, pixels) = svg::rasterize(path, &vfs.read(path)?, size, scale)?;
+        Self::create_rgba_texture(texture_creator, size, &pixels)
+    }
+
+    fn create_rgba_texture(
+        texture_creator: &TextureCreator<WindowContext>,
+        (width, height): (u32, u32),
+        pixels: &[u8],
+    ) -> MgiResult<TextureRaw> {
+        let mut raw =
+            texture_creator.create_texture_static(PixelFormatEnum::RGBA32, width, height)?;
+        raw.update(None, pixels, width as usize * 4)?;
+        raw.set_blend_mode(sdl2::render::BlendMode::Blend);
+
+        Ok(raw)
+    }
+
+    /// Drawn in place of missing textures, see `MissingAssetPolicy`
+    pub(crate) fn create_placeholder(&self) -> MgiResult<TextureRaw> {
+        Self::create_rgba_texture(
+            self.texture_creator.as_ref().unwrap(),
+            (16, 16),
+            &missing_asset::placeholder_pixels(),
+        )
+    }
+
+    /// Creates a texture that can be used as a render target
+    pub(crate) fn add_render_target(
+        &mut self,
+        name: &str,
+        size: (u32, u32),
+    ) -> MgiResult<Handle<Texture>> {
+        if self.textures.handle(name).is_some() {
+            return Err(format!("A texture named `{}` already exists", name).into());
+        }
+
+        let mut raw = self
+            .texture_creator
+            .as_ref()
+            .ok_or("Render targets can only be created once the game is running")?
+            .create_texture_target(PixelFormatEnum::RGBA8888, size.0, size.1)?;
+
+        // Render targets start out transparent, so they must be alpha blended when drawn
+        raw.set_blend_mode(sdl2::render::BlendMode::Blend);
+
+        Ok(self
+            .textures
+            .insert(name, Texture::new(name, "", Some(raw))))
+    }
+
+    /// Creates a texture whose pixels are written by a `PixelBuffer`
+    pub(crate) fn add_streaming_texture(
+        &mut self,
+        name: &str,
+        size: (u32, u32),
+    ) -> MgiResult<Handle<Texture>> {
+        if self.textures.handle(name).is_some() {
+            return Err(format!("A texture named `{}` already exists", name).into());
+        }
+
+        let mut raw = self
+            .texture_creator
+            .as_ref()
+            .ok_or("Pixel buffers can only be created once the game is running")?
+            .create_texture_streaming(PixelFormatEnum::RGBA32, size.0, size.1)?;
+        raw.set_blend_mode(sdl2::render::BlendMode::Blend);
+
+        Ok(self
+            .textures
+            .insert(name, Texture::new(name, "", Some(raw))))
+    }
+
+    pub(crate) fn get_texture(&self, key: &AssetKey<Texture>) -> MgiResult<&Texture> {
+        self.textures.get(key)
+    }
+
+    /// The raw texture and the texture's region of it, which are only created once the game is
+    /// running
+    pub(crate) fn get_region(&self, key: &AssetKey<Texture>) -> MgiResult<TextureRegion> {
+        Ok(TextureRegion {
+            raw: Rc::clone(self.get_raw(key)?),
+            rect: self.get_texture(key)?.region,
+        })
+    }
 
-        None
+    /// The raw texture, which is only created once the game is running
+    pub(crate) fn get_raw(&self, key: &AssetKey<Texture>) -> MgiResult<&Rc<RefCell<TextureRaw>>> {
+        self.get_texture(key)?
+            .raw
+            .as_ref()
+            .ok_or_else(|| format!("The texture {:?} was not loaded", key).into())
     }
 }
@@ -1,11 +1,9 @@
-use sdl2::pixels::Color;
-
-use crate::prelude::{Rectangle, Rotation, Vec2};
+use crate::prelude::{Rect, Rotation, Vec2};
 
 // TODO: Add support for name tilesets inside tilemaps (instead of usize, and convert the name to
 // usize)
 pub struct Tile {
-    pub(crate) rect: Rectangle,
+    pub(crate) rect: Rect,
     pub(crate) rotation: Rotation,
     pub(crate) texture_idx: (usize, usize),
 }
@@ -33,8 +31,6 @@ pub type TilePlacementFn = Box<dyn Fn(i32, i32) -> (usize, usize)>;
 // TODO: Make sure tile_placement_fn is set before rendering TileMap
 // TODO: Add capability for differently sized tiles?
 pub struct TileMap {
-    pub(crate) id: usize,
-
     pub(crate) nrows: usize,
     pub(crate) ncols: usize,
 
@@ -53,7 +49,6 @@ pub struct TileMap {
 impl TileMap {
     pub fn new(nrows: usize, ncols: usize, tile_size: (usize, usize)) -> Self {
         Self {
-            id: 0,
             ncols,
             nrows,
             tile_size,
@@ -95,11 +90,10 @@ impl TileMap {
                 let tile_pos = (x * self.tile_size.0, y * self.tile_size.1);
 
                 let tile = Tile {
-                    rect: Rectangle::new(
+                    rect: Rect::new(
                         Vec2::new(tile_pos.0 as i32, tile_pos.1 as i32),
                         self.tile_size.0 as u32,
                         self.tile_size.1 as u32,
-                        Color::WHITE,
                     ),
                     rotation: Rotation::Degrees(0.),
                     texture_idx,
@@ -0,0 +1,211 @@
+use std::ops::Mul;
+
+use crate::prelude::{Rotation, Vec2f};
+
+/// A 2D affine transformation matrix.
+///
+/// A point `(x, y)` is transformed to `(a * x + c * y + tx, b * x + d * y + ty)`, and `lhs * rhs`
+/// applies `rhs` first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix2D {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Matrix2D {
+    pub const IDENTITY: Matrix2D = Matrix2D {
+        a: 1.,
+        b: 0.,
+        c: 0.,
+        d: 1.,
+        tx: 0.,
+        ty: 0.,
+    };
+
+    pub fn translation(translation: Vec2f) -> Self {
+        Self {
+            tx: translation.x,
+            ty: translation.y,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn rotation(rotation: Rotation) -> Self {
+        let (sin, cos) = rotation.to_radians().sin_cos();
+
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scale(scale: Vec2f) -> Self {
+        Self {
+            a: scale.x,
+            d: scale.y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Skews along the x axis by `x`, and along the y axis by `y`
+    pub fn skew(x: Rotation, y: Rotation) -> Self {
+        Self {
+            b: y.to_radians().tan(),
+            c: x.to_radians().tan(),
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn transform_point(&self, point: Vec2f) -> Vec2f {
+        Vec2f::new(
+            self.a * point.x + self.c * point.y + self.tx,
+            self.b * point.x + self.d * point.y + self.ty,
+        )
+    }
+
+    /// Transforms a direction (the translation isn't applied)
+    pub fn transform_vector(&self, vector: Vec2f) -> Vec2f {
+        Vec2f::new(
+            self.a * vector.x + self.c * vector.y,
+            self.b * vector.x + self.d * vector.y,
+        )
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Returns `None` if the matrix can't be inverted (e.g. it has a scale of zero)
+    pub fn inverse(&self) -> Option<Matrix2D> {
+        let det = self.determinant();
+        if det == 0. {
+            return None;
+        }
+
+        Some(Matrix2D {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+
+    /// Splits the matrix into its translation, rotation, and scale.
+    ///
+    /// Any skew can't be represented by these, so it is lost.
+    pub fn decompose(&self) -> (Vec2f, Rotation, Vec2f) {
+        let scale_x = (self.a * self.a + self.b * self.b).sqrt();
+        let scale_y = if scale_x == 0. {
+            (self.c * self.c + self.d * self.d).sqrt()
+        } else {
+            self.determinant() / scale_x
+        };
+
+        (
+            Vec2f::new(self.tx, self.ty),
+            Rotation::Radians(self.b.atan2(self.a)),
+            Vec2f::new(scale_x, scale_y),
+        )
+    }
+}
+
+impl Default for Matrix2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Matrix2D {
+    type Output = Matrix2D;
+
+    fn mul(self, rhs: Matrix2D) -> Matrix2D {
+        Matrix2D {
+            a: self.a * rhs.a + self.c * rhs.b,
+            b: self.b * rhs.a + self.d * rhs.b,
+            c: self.a * rhs.c + self.c * rhs.d,
+            d: self.b * rhs.c + self.d * rhs.d,
+            tx: self.a * rhs.tx + self.c * rhs.ty + self.tx,
+            ty: self.b * rhs.tx + self.d * rhs.ty + self.ty,
+        }
+    }
+}
+
+/// The translation, rotation, scale, and skew of an object.
+///
+/// These are applied in the order: scale, skew, rotation, translation.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform2D {
+    pub translation: Vec2f,
+    pub rotation: Rotation,
+    pub scale: Vec2f,
+    pub skew: (Rotation, Rotation),
+}
+
+impl Transform2D {
+    pub fn new(translation: impl Into<Vec2f>) -> Self {
+        Self {
+            translation: translation.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn scale(mut self, scale: impl Into<Vec2f>) -> Self {
+        self.scale = scale.into();
+        self
+    }
+
+    pub fn skew(mut self, x: Rotation, y: Rotation) -> Self {
+        self.skew = (x, y);
+        self
+    }
+
+    pub fn matrix(&self) -> Matrix2D {
+        Matrix2D::translation(self.translation)
+            * Matrix2D::rotation(self.rotation)
+            * Matrix2D::skew(self.skew.0, self.skew.1)
+            * Matrix2D::scale(self.scale)
+    }
+
+    pub fn transform_point(&self, point: Vec2f) -> Vec2f {
+        self.matrix().transform_point(point)
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self {
+            translation: Vec2f::ZERO,
+            rotation: Rotation::Radians(0.),
+            scale: Vec2f::new(1., 1.),
+            skew: (Rotation::Radians(0.), Rotation::Radians(0.)),
+        }
+    }
+}
+
+impl Mul for Transform2D {
+    type Output = Matrix2D;
+
+    fn mul(self, rhs: Transform2D) -> Matrix2D {
+        self.matrix() * rhs.matrix()
+    }
+}
+
+impl From<Transform2D> for Matrix2D {
+    fn from(transform: Transform2D) -> Self {
+        transform.matrix()
+    }
+}
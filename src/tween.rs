@@ -0,0 +1,535 @@
+use std::{cell::RefCell, f32::consts::PI, rc::Rc};
+
+use crate::prelude::Lerp;
+
+/// Easing functions, mapping the linear progress of a tween to its eased progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    /// Eases `t` (in the range `0.0..=1.0`); elastic and back easings overshoot that range
+    pub fn apply(self, t: f32) -> f32 {
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+        const ELASTIC: f32 = 2. * PI / 3.;
+        const ELASTIC_IN_OUT: f32 = 2. * PI / 4.5;
+
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1. - (1. - t) * (1. - t),
+            Easing::QuadInOut if t < 0.5 => 2. * t * t,
+            Easing::QuadInOut => 1. - (-2. * t + 2.).powi(2) / 2.,
+
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1. - (1. - t).powi(3),
+            Easing::CubicInOut if t < 0.5 => 4. * t * t * t,
+            Easing::CubicInOut => 1. - (-2. * t + 2.).powi(3) / 2.,
+
+            Easing::ElasticIn | Easing::ElasticOut | Easing::ElasticInOut if t == 0. || t == 1. => {
+                t
+            }
+            Easing::ElasticIn => -(2f32.powf(10. * t - 10.)) * ((t * 10. - 10.75) * ELASTIC).sin(),
+            Easing::ElasticOut => 2f32.powf(-10. * t) * ((t * 10. - 0.75) * ELASTIC).sin() + 1.,
+            Easing::ElasticInOut if t < 0.5 => {
+                -(2f32.powf(20. * t - 10.) * ((20. * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.
+            }
+            Easing::ElasticInOut => {
+                2f32.powf(-20. * t + 10.) * ((20. * t - 11.125) * ELASTIC_IN_OUT).sin() / 2. + 1.
+            }
+
+            Easing::BackIn => (BACK + 1.) * t * t * t - BACK * t * t,
+            Easing::BackOut => 1. + (BACK + 1.) * (t - 1.).powi(3) + BACK * (t - 1.).powi(2),
+            Easing::BackInOut if t < 0.5 => {
+                (2. * t).powi(2) * ((BACK_IN_OUT + 1.) * 2. * t - BACK_IN_OUT) / 2.
+            }
+            Easing::BackInOut => {
+                ((2. * t - 2.).powi(2) * ((BACK_IN_OUT + 1.) * (t * 2. - 2.) + BACK_IN_OUT) + 2.)
+                    / 2.
+            }
+
+            Easing::BounceIn => 1. - Easing::BounceOut.apply(1. - t),
+            Easing::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+
+                if t < 1. / D {
+                    N * t * t
+                } else if t < 2. / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+            Easing::BounceInOut if t < 0.5 => (1. - Easing::BounceOut.apply(1. - 2. * t)) / 2.,
+            Easing::BounceInOut => (1. + Easing::BounceOut.apply(2. * t - 1.)) / 2.,
+        }
+    }
+}
+
+/// A value that is animated by tweens.
+///
+/// This is a shared handle (cloning it is cheap), so the game can keep one copy to read the value
+/// from while the tween, played through the `Context`, writes to another.
+#[derive(Debug, Clone, Default)]
+pub struct TweenValue<T: Clone>(Rc<RefCell<T>>);
+
+impl<T: Clone> TweenValue<T> {
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(RefCell::new(value)))
+    }
+
+    pub fn get(&self) -> T {
+        self.0.borrow().clone()
+    }
+
+    pub fn set(&self, value: T) {
+        *self.0.borrow_mut() = value;
+    }
+}
+
+/// Something that progresses over time, played through `Context::play`
+pub trait Animation {
+    /// Advances the animation by `dt` seconds, returning the time left over once it has finished
+    /// (so that a following animation can use it), or `None` if it is still running.
+    fn advance(&mut self, dt: f32) -> Option<f32>;
+
+    /// Restarts the animation from the beginning
+    fn reset(&mut self);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Repeat {
+    Times(u32),
+    Forever,
+}
+
+/// Animates a `TweenValue` from one value to another.
+pub struct Tween<T: Lerp + Clone> {
+    target: TweenValue<T>,
+    from: Option<T>,
+    to: T,
+
+    /// The value the tween starts from; this is `from`, or the target's value when the tween first
+    /// advances if `from` wasn't set
+    start: Option<T>,
+
+    duration: f32,
+    easing: Easing,
+    repeat: Repeat,
+    yoyo: bool,
+
+    elapsed: f32,
+    iteration: u32,
+    finished: bool,
+
+    on_complete: Option<Box<dyn FnMut()>>,
+}
+
+impl<T: Lerp + Clone> Tween<T> {
+    /// Tweens the target from its current value to `to`, over `duration` seconds
+    pub fn new(target: &TweenValue<T>, to: T, duration: f32) -> Self {
+        Self {
+            target: target.clone(),
+            from: None,
+            to,
+            start: None,
+            duration,
+            easing: Easing::Linear,
+            repeat: Repeat::Times(0),
+            yoyo: false,
+            elapsed: 0.,
+            iteration: 0,
+            finished: false,
+            on_complete: None,
+        }
+    }
+
+    pub fn from(mut self, from: T) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Plays the tween `count` more times after the first time
+    pub fn repeat(mut self, count: u32) -> Self {
+        self.repeat = Repeat::Times(count);
+        self
+    }
+
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeat = Repeat::Forever;
+        self
+    }
+
+    /// Plays every other repetition backwards
+    pub fn yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Called when the tween (including all of its repetitions) has finished
+    pub fn on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    fn has_repeats_left(&self) -> bool {
+        match self.repeat {
+            Repeat::Times(count) => self.iteration < count,
+            Repeat::Forever => true,
+        }
+    }
+
+    fn apply(&mut self, t: f32) {
+        let start = self
+            .start
+            .get_or_insert_with(|| self.from.clone().unwrap_or_else(|| self.target.get()));
+
+        let backwards = self.yoyo && self.iteration % 2 == 1;
+        let t = if backwards { 1. - t } else { t };
+
+        self.target.set(start.lerp(&self.to, self.easing.apply(t)));
+    }
+}
+
+impl<T: Lerp + Clone> Animation for Tween<T> {
+    fn advance(&mut self, dt: f32) -> Option<f32> {
+        if self.finished {
+            return Some(dt);
+        }
+
+        self.elapsed += dt;
+        while self.elapsed >= self.duration {
+            if !self.has_repeats_left() {
+                let leftover = self.elapsed - self.duration;
+                self.apply(1.);
+                self.finished = true;
+
+                if let Some(on_complete) = &mut self.on_complete {
+                    on_complete();
+                }
+
+                return Some(leftover);
+            }
+
+            self.elapsed -= self.duration;
+            self.iteration += 1;
+
+            // Zero length tweens would otherwise repeat forever in a single frame (and have no
+            // progress in between)
+            if self.duration <= 0. {
+                self.elapsed = 0.;
+                self.apply(1.);
+                return None;
+            }
+        }
+
+        self.apply(self.elapsed / self.duration);
+
+        None
+    }
+
+    fn reset(&mut self) {
+        self.start = None;
+        self.elapsed = 0.;
+        self.iteration = 0;
+        self.finished = false;
+    }
+}
+
+/// Plays animations one after another
+#[derive(Default)]
+pub struct Sequence {
+    animations: Vec<Box<dyn Animation>>,
+    current: usize,
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, animation: impl Animation + 'static) -> Self {
+        self.animations.push(Box::new(animation));
+        self
+    }
+
+    /// Waits for `duration` seconds before playing the next animation
+    pub fn wait(self, duration: f32) -> Self {
+        self.then(Delay::new(duration))
+    }
+}
+
+impl Animation for Sequence {
+    fn advance(&mut self, mut dt: f32) -> Option<f32> {
+        while let Some(animation) = self.animations.get_mut(self.current) {
+            dt = animation.advance(dt)?;
+            self.current += 1;
+        }
+
+        Some(dt)
+    }
+
+    fn reset(&mut self) {
+        self.current = 0;
+        for animation in &mut self.animations {
+            animation.reset();
+        }
+    }
+}
+
+/// Plays animations at the same time, finishing once all of them have finished
+#[derive(Default)]
+pub struct Parallel {
+    animations: Vec<(Box<dyn Animation>, Option<f32>)>,
+}
+
+impl Parallel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, animation: impl Animation + 'static) -> Self {
+        self.animations.push((Box::new(animation), None));
+        self
+    }
+}
+
+impl Animation for Parallel {
+    fn advance(&mut self, dt: f32) -> Option<f32> {
+        for (animation, finished) in &mut self.animations {
+            *finished = match finished {
+                None => animation.advance(dt),
+
+                // Animations that already finished have all of this frame left over
+                Some(_) => Some(dt),
+            };
+        }
+
+        // Finishes with the time left over by the animation that finished last
+        self.animations
+            .iter()
+            .try_fold(dt, |leftover, (_, finished)| {
+                Some(leftover.min((*finished)?))
+            })
+    }
+
+    fn reset(&mut self) {
+        for (animation, finished) in &mut self.animations {
+            animation.reset();
+            *finished = None;
+        }
+    }
+}
+
+/// An animation that does nothing for a while
+pub struct Delay {
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Delay {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            elapsed: 0.,
+        }
+    }
+}
+
+impl Animation for Delay {
+    fn advance(&mut self, dt: f32) -> Option<f32> {
+        self.elapsed += dt;
+
+        if self.elapsed >= self.duration {
+            Some(self.elapsed - self.duration)
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.elapsed = 0.;
+    }
+}
+
+/// Identifies an animation played through `Context::play`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnimationId(u64);
+
+/// The animations being played by the `Context`
+#[derive(Default)]
+pub(crate) struct Animations {
+    playing: Vec<(AnimationId, Box<dyn Animation>)>,
+    finished: Vec<AnimationId>,
+    next_id: u64,
+}
+
+impl Animations {
+    pub(crate) fn play(&mut self, animation: Box<dyn Animation>) -> AnimationId {
+        let id = AnimationId(self.next_id);
+        self.next_id += 1;
+        self.playing.push((id, animation));
+
+        id
+    }
+
+    pub(crate) fn stop(&mut self, id: AnimationId) {
+        self.playing.retain(|(playing_id, _)| *playing_id != id);
+    }
+
+    pub(crate) fn is_playing(&self, id: AnimationId) -> bool {
+        self.playing.iter().any(|(playing_id, _)| *playing_id == id)
+    }
+
+    pub(crate) fn finished(&self) -> &[AnimationId] {
+        &self.finished
+    }
+
+    /// Advances every animation, and records the ones that finished this frame
+    pub(crate) fn update(&mut self, dt: f32) {
+        self.finished.clear();
+
+        let finished = &mut self.finished;
+        self.playing.retain_mut(|(id, animation)| {
+            if animation.advance(dt).is_some() {
+                finished.push(*id);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASINGS: [Easing; 16] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in EASINGS {
+            assert!(easing.apply(0.).abs() < 1e-4, "{:?}", easing);
+            assert!((easing.apply(1.) - 1.).abs() < 1e-4, "{:?}", easing);
+            assert_eq!(easing.apply(-1.), easing.apply(0.), "{:?}", easing);
+            assert_eq!(easing.apply(2.), easing.apply(1.), "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn in_out_easings_are_halfway_at_the_middle() {
+        for easing in [
+            Easing::Linear,
+            Easing::QuadInOut,
+            Easing::CubicInOut,
+            Easing::ElasticInOut,
+            Easing::BackInOut,
+            Easing::BounceInOut,
+        ] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-4, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn tween_reaches_its_target_and_returns_the_leftover_time() {
+        let value = TweenValue::new(0.);
+        let mut tween = Tween::new(&value, 10., 1.);
+
+        assert_eq!(tween.advance(0.5), None);
+        assert_eq!(value.get(), 5.);
+        assert_eq!(tween.advance(0.75), Some(0.25));
+        assert_eq!(value.get(), 10.);
+    }
+
+    #[test]
+    fn yoyo_plays_every_other_repetition_backwards() {
+        let value = TweenValue::new(0.);
+        let mut tween = Tween::new(&value, 10., 1.).repeat(1).yoyo(true);
+
+        assert_eq!(tween.advance(1.25), None);
+        assert_eq!(value.get(), 7.5);
+        assert_eq!(tween.advance(0.75), Some(0.));
+        assert_eq!(value.get(), 0.);
+    }
+
+    #[test]
+    fn zero_length_tweens_apply_their_target() {
+        let value = TweenValue::new(0.);
+        let mut tween = Tween::new(&value, 10., 0.).repeat_forever();
+
+        assert_eq!(tween.advance(0.1), None);
+        assert_eq!(value.get(), 10.);
+
+        let mut tween = Tween::new(&value, 20., 0.);
+        assert_eq!(tween.advance(0.1), Some(0.1));
+        assert_eq!(value.get(), 20.);
+    }
+
+    #[test]
+    fn sequences_pass_the_leftover_time_on() {
+        let value = TweenValue::new(0.);
+        let mut sequence = Sequence::new()
+            .then(Delay::new(1.))
+            .then(Tween::new(&value, 10., 1.));
+
+        assert_eq!(sequence.advance(1.5), None);
+        assert_eq!(value.get(), 5.);
+        assert_eq!(sequence.advance(1.), Some(0.5));
+    }
+
+    #[test]
+    fn parallel_finishes_with_the_longest_animation() {
+        let mut parallel = Parallel::new().with(Delay::new(1.)).with(Delay::new(2.));
+
+        assert_eq!(parallel.advance(1.5), None);
+        assert_eq!(parallel.advance(1.), Some(0.5));
+    }
+}
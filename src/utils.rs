@@ -1,8 +1,14 @@
-use std::{error::Error, f32::consts::PI};
+use std::{
+    error::Error,
+    f32::consts::PI,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use sdl2::pixels::Color;
 
 pub type MgiResult<T> = Result<T, Box<dyn Error>>;
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub enum Rotation {
     Degrees(f32),
     Radians(f32),
@@ -24,6 +30,36 @@ impl Rotation {
     }
 }
 
+/// Linear interpolation between two values, where `t` is in the range `0.0..=1.0`
+pub trait Lerp {
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let channel = |a: u8, b: u8| (a as f32).lerp(&(b as f32), t).round() as u8;
+
+        Color::RGBA(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            channel(self.a, other.a),
+        )
+    }
+}
+
+impl Lerp for Rotation {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Rotation::Radians(self.to_radians().lerp(&other.to_radians(), t))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Vec2 {
     pub x: i32,
@@ -36,15 +72,22 @@ impl Vec2 {
     }
 }
 
+/// Rounds to the nearest pixel
+impl Lerp for Vec2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec2f::from(*self).lerp(&Vec2f::from(*other), t).into()
+    }
+}
+
 impl From<(i32, i32)> for Vec2 {
     fn from(v: (i32, i32)) -> Self {
         Self { x: v.0, y: v.1 }
     }
 }
 
-impl Into<(i32, i32)> for Vec2 {
-    fn into(self) -> (i32, i32) {
-        (self.x, self.y)
+impl From<Vec2> for (i32, i32) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
     }
 }
 
@@ -57,23 +100,206 @@ impl From<(u32, u32)> for Vec2 {
     }
 }
 
-impl Into<(u32, u32)> for Vec2 {
-    fn into(self) -> (u32, u32) {
-        (self.x as u32, self.y as u32)
+impl From<Vec2> for (u32, u32) {
+    fn from(v: Vec2) -> Self {
+        (v.x as u32, v.y as u32)
     }
 }
 
+/// Rounds to the nearest pixel, like the conversion from `Vec2f`
 impl From<(f32, f32)> for Vec2 {
     fn from(v: (f32, f32)) -> Self {
-        Self {
-            x: v.0 as i32,
-            y: v.1 as i32,
+        Vec2f::from(v).into()
+    }
+}
+
+impl From<Vec2> for (f32, f32) {
+    fn from(v: Vec2) -> Self {
+        (v.x as f32, v.y as f32)
+    }
+}
+
+/// A 2D vector with `f32` components, for sub-pixel positions, velocities, and directions
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2f {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2f {
+    pub const ZERO: Vec2f = Vec2f { x: 0., y: 0. };
+
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(&self, other: Vec2f) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product (the signed area of the parallelogram)
+    pub fn cross(&self, other: Vec2f) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn length_squared(&self) -> f32 {
+        self.dot(*self)
+    }
+
+    /// Returns the unit vector in the same direction (or zero if the length is zero)
+    pub fn normalize(&self) -> Vec2f {
+        let len = self.length();
+        if len == 0. {
+            return Vec2f::ZERO;
         }
+
+        *self / len
+    }
+
+    pub fn distance(&self, other: Vec2f) -> f32 {
+        (other - *self).length()
+    }
+
+    /// Rotates the vector clockwise (in screen coordinates, where y points down)
+    pub fn rotate(&self, rotation: Rotation) -> Vec2f {
+        let (sin, cos) = rotation.to_radians().sin_cos();
+
+        Vec2f::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// The signed angle to rotate this vector by to point in the same direction as `other`
+    pub fn angle_to(&self, other: Vec2f) -> Rotation {
+        Rotation::Radians(self.cross(other).atan2(self.dot(other)))
     }
 }
 
-impl Into<(f32, f32)> for Vec2 {
-    fn into(self) -> (f32, f32) {
-        (self.x as f32, self.y as f32)
+impl Lerp for Vec2f {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec2f::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t))
+    }
+}
+
+impl Add for Vec2f {
+    type Output = Vec2f;
+
+    fn add(self, rhs: Vec2f) -> Vec2f {
+        Vec2f::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2f {
+    fn add_assign(&mut self, rhs: Vec2f) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2f {
+    type Output = Vec2f;
+
+    fn sub(self, rhs: Vec2f) -> Vec2f {
+        Vec2f::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2f {
+    fn sub_assign(&mut self, rhs: Vec2f) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for Vec2f {
+    type Output = Vec2f;
+
+    fn mul(self, rhs: f32) -> Vec2f {
+        Vec2f::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// Component-wise multiplication
+impl Mul<Vec2f> for Vec2f {
+    type Output = Vec2f;
+
+    fn mul(self, rhs: Vec2f) -> Vec2f {
+        Vec2f::new(self.x * rhs.x, self.y * rhs.y)
+    }
+}
+
+impl MulAssign<f32> for Vec2f {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<f32> for Vec2f {
+    type Output = Vec2f;
+
+    fn div(self, rhs: f32) -> Vec2f {
+        Vec2f::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl DivAssign<f32> for Vec2f {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
+impl Neg for Vec2f {
+    type Output = Vec2f;
+
+    fn neg(self) -> Vec2f {
+        Vec2f::new(-self.x, -self.y)
+    }
+}
+
+impl From<Vec2> for Vec2f {
+    fn from(v: Vec2) -> Self {
+        Self::new(v.x as f32, v.y as f32)
+    }
+}
+
+/// Rounds to the nearest pixel
+impl From<Vec2f> for Vec2 {
+    fn from(v: Vec2f) -> Self {
+        Self::new(v.x.round() as i32, v.y.round() as i32)
+    }
+}
+
+impl From<(f32, f32)> for Vec2f {
+    fn from(v: (f32, f32)) -> Self {
+        Self::new(v.0, v.1)
+    }
+}
+
+impl From<(i32, i32)> for Vec2f {
+    fn from(v: (i32, i32)) -> Self {
+        Self::new(v.0 as f32, v.1 as f32)
+    }
+}
+
+impl From<Vec2f> for (f32, f32) {
+    fn from(v: Vec2f) -> Self {
+        (v.x, v.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_points_round_to_the_same_pixel() {
+        for point in [(0.4, 0.6), (-0.6, 2.5), (99.99, -0.49)] {
+            let from_tuple = Vec2::from(point);
+            let from_vec2f = Vec2::from(Vec2f::from(point));
+            assert_eq!((from_tuple.x, from_tuple.y), (from_vec2f.x, from_vec2f.y));
+        }
+
+        let rounded = Vec2::from((0.6, -0.6));
+        assert_eq!((rounded.x, rounded.y), (1, -1));
     }
 }
@@ -0,0 +1,208 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use crate::{pack::Pack, prelude::MgiResult};
+
+enum MountSource {
+    Directory(PathBuf),
+    Pack(Pack),
+}
+
+struct Mount {
+    priority: i32,
+    source: MountSource,
+}
+
+/// The virtual filesystem every asset is loaded through.
+///
+/// Directories and packs (made with the `mgi-pack` tool) are mounted with a priority: a file is
+/// read from the mount with the highest priority that contains it, so e.g. a mods directory can
+/// override files of the game's pack. Mounts with the same priority are searched from the most
+/// recently mounted one. Paths that aren't found in any mount are read from the filesystem
+/// directly.
+///
+/// The filesystem is shared (cloning it is cheap), so mounting a directory affects every clone.
+#[derive(Clone, Default)]
+pub struct Vfs {
+    mounts: Arc<RwLock<Vec<Mount>>>,
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts the directory, so the files inside it can be read with paths relative to it
+    pub fn mount_directory(&self, directory: &str, priority: i32) -> MgiResult<()> {
+        if !Path::new(directory).is_dir() {
+            return Err(format!("Couldn't mount `{}`: it is not a directory", directory).into());
+        }
+
+        self.mount(priority, MountSource::Directory(directory.into()));
+
+        Ok(())
+    }
+
+    /// Mounts a pack made with the `mgi-pack` tool, so the files inside it can be read with paths
+    /// relative to the directory it was made from
+    pub fn mount_pack(&self, path: &str, priority: i32) -> MgiResult<()> {
+        let pack = Pack::open(Path::new(path))
+            .map_err(|e| format!("Couldn't mount the pack `{}`: {}", path, e))?;
+        self.mount(priority, MountSource::Pack(pack));
+
+        Ok(())
+    }
+
+    fn mount(&self, priority: i32, source: MountSource) {
+        let mut mounts = self.mounts.write().unwrap();
+
+        // Kept sorted from the highest priority
+        let idx = mounts
+            .iter()
+            .position(|mount| mount.priority <= priority)
+            .unwrap_or(mounts.len());
+        mounts.insert(idx, Mount { priority, source });
+    }
+
+    pub fn read(&self, path: &str) -> MgiResult<Vec<u8>> {
+        let normalized = crate::pack::normalize(path);
+
+        for mount in self.mounts.read().unwrap().iter() {
+            let data = match &mount.source {
+                MountSource::Directory(directory) => {
+                    let file = directory.join(&normalized);
+                    if !file.is_file() {
+                        continue;
+                    }
+                    fs::read(file)
+                }
+                MountSource::Pack(pack) => match pack.read(&normalized) {
+                    Some(data) => data,
+                    None => continue,
+                },
+            };
+
+            return Ok(data.map_err(|e| format!("Couldn't read `{}`: {}", path, e))?);
+        }
+
+        Ok(fs::read(path).map_err(|e| format!("Couldn't read `{}`: {}", path, e))?)
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        let normalized = crate::pack::normalize(path);
+
+        self.mounts
+            .read()
+            .unwrap()
+            .iter()
+            .any(|mount| match &mount.source {
+                MountSource::Directory(directory) => directory.join(&normalized).is_file(),
+                MountSource::Pack(pack) => pack.contains(&normalized),
+            })
+            || Path::new(path).is_file()
+    }
+
+    /// The file on disk that the path is read from, or `None` if it is read from a pack or
+    /// doesn't exist
+    pub fn disk_path(&self, path: &str) -> Option<PathBuf> {
+        let normalized = crate::pack::normalize(path);
+
+        for mount in self.mounts.read().unwrap().iter() {
+            match &mount.source {
+                MountSource::Directory(directory) => {
+                    let file = directory.join(&normalized);
+                    if file.is_file() {
+                        return Some(file);
+                    }
+                }
+                MountSource::Pack(pack) if pack.contains(&normalized) => return None,
+                MountSource::Pack(_) => {}
+            }
+        }
+
+        Path::new(path).is_file().then(|| path.into())
+    }
+}
+
+/// Packs every file inside `directory` (recursively) into a pack that can be mounted with
+/// `Vfs::mount_pack`, returning the number of files. This is what the `mgi-pack` tool does.
+pub fn write_pack(directory: &str, output: &str) -> MgiResult<usize> {
+    Ok(crate::pack::write(Path::new(directory), Path::new(output))
+        .map_err(|e| format!("Couldn't pack `{}` into `{}`: {}", directory, output, e))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pack::tests::TempDir;
+
+    fn mount_with(vfs: &Vfs, dir: &TempDir, name: &str, content: &str, priority: i32) {
+        let directory = dir.0.join(name);
+        fs::create_dir_all(directory.join("sprites")).unwrap();
+        fs::write(directory.join("sprites/player.png"), content).unwrap();
+        vfs.mount_directory(directory.to_str().unwrap(), priority)
+            .unwrap();
+    }
+
+    fn read(vfs: &Vfs, path: &str) -> String {
+        String::from_utf8(vfs.read(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn reads_from_the_highest_priority() {
+        let dir = TempDir::new("vfs-priority");
+        let vfs = Vfs::new();
+        mount_with(&vfs, &dir, "mods", "mod", 10);
+        mount_with(&vfs, &dir, "base", "base", 0);
+        assert_eq!(read(&vfs, "sprites/player.png"), "mod");
+
+        // With the same priority, the most recent mount wins
+        mount_with(&vfs, &dir, "patch", "patch", 10);
+        assert_eq!(read(&vfs, "sprites/player.png"), "patch");
+        assert_eq!(read(&vfs, "./sprites/../sprites/player.png"), "patch");
+    }
+
+    #[test]
+    fn packs_override_directories() {
+        let dir = TempDir::new("vfs-pack");
+        let vfs = Vfs::new();
+        mount_with(&vfs, &dir, "base", "base", 0);
+        assert!(vfs.disk_path("sprites/player.png").is_some());
+
+        fs::create_dir_all(dir.0.join("packed/sprites")).unwrap();
+        fs::write(dir.0.join("packed/sprites/player.png"), "packed").unwrap();
+        let pack = dir.0.join("game.pack");
+        write_pack(
+            dir.0.join("packed").to_str().unwrap(),
+            pack.to_str().unwrap(),
+        )
+        .unwrap();
+        vfs.mount_pack(pack.to_str().unwrap(), 5).unwrap();
+
+        assert_eq!(read(&vfs, "sprites/player.png"), "packed");
+        assert!(vfs.exists("sprites/player.png"));
+        assert!(vfs.disk_path("sprites/player.png").is_none());
+    }
+
+    #[test]
+    fn falls_back_to_the_filesystem() {
+        let dir = TempDir::new("vfs-fallback");
+        let vfs = Vfs::new();
+        mount_with(&vfs, &dir, "base", "base", 0);
+
+        let file = dir.0.join("outside.txt");
+        fs::write(&file, "outside").unwrap();
+        let file = file.to_str().unwrap();
+        assert_eq!(read(&vfs, file), "outside");
+        assert!(vfs.exists(file));
+
+        assert!(!vfs.exists("sprites/missing.png"));
+        assert!(vfs.read("sprites/missing.png").is_err());
+        assert!(vfs
+            .mount_directory(dir.0.join("missing").to_str().unwrap(), 0)
+            .is_err());
+    }
+}